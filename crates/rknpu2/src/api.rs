@@ -16,8 +16,15 @@ use {
         RKNN_FLAG_EXECUTE_FALLBACK_PRIOR_DEVICE_GPU, RKNN_FLAG_FENCE_IN_OUTSIDE,
         RKNN_FLAG_FENCE_OUT_OUTSIDE, RKNN_FLAG_INTERNAL_ALLOC_OUTSIDE, RKNN_FLAG_MEM_ALLOC_OUTSIDE,
         RKNN_FLAG_MODEL_BUFFER_ZERO_COPY, RKNN_FLAG_PRIOR_HIGH, RKNN_FLAG_PRIOR_LOW,
-        RKNN_FLAG_PRIOR_MEDIUM, RKNN_FLAG_SHARE_SRAM, RKNN_FLAG_SHARE_WEIGHT_MEM, rknn_context,
-        rknn_mem_sync_mode, rknn_query_cmd, rknn_run_extend, rknn_tensor_attr, rknn_tensor_mem,
+        RKNN_FLAG_PRIOR_MEDIUM, RKNN_FLAG_SHARE_SRAM, RKNN_FLAG_SHARE_WEIGHT_MEM,
+        RKNN_MEM_FLAG_ALLOC_NO_CONTEXT,
+        _rknn_mem_alloc_flags::{
+            RKNN_FLAG_MEMORY_CACHEABLE, RKNN_FLAG_MEMORY_NON_CACHEABLE,
+            RKNN_FLAG_MEMORY_TRY_ALLOC_SRAM,
+        },
+        rknn_context,
+        rknn_init_extend, rknn_mem_sync_mode, rknn_query_cmd, rknn_run_extend, rknn_tensor_attr,
+        rknn_tensor_mem,
     },
     std::ffi::{c_int, c_void},
 };
@@ -32,6 +39,24 @@ use crate::Error;
 
 pub trait RKNNAPI {
     // ───── core runtime ──────────────────────────────────────────────────────
+    /// Create a context with `rknn_init`.
+    ///
+    /// # Safety
+    ///
+    /// `context` must be valid for writes. `model` must point to `size` bytes
+    /// of model data, or to a NUL-terminated path if `size` is 0, and stay
+    /// valid for the context's lifetime if `flag` includes
+    /// [`RknnInitFlags::MODEL_BUFFER_ZERO_COPY`]. `extend` must be null or
+    /// valid for reads and writes.
+    unsafe fn init(
+        &self,
+        context: *mut rknn_context,
+        model: *mut c_void,
+        size: u32,
+        flag: u32,
+        extend: *mut rknn_init_extend,
+    ) -> Result<c_int, Error>;
+
     #[cfg_attr(
         feature = "docs",
         doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
@@ -451,6 +476,7 @@ impl RknnInitFlags {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Priority {
     Low,
     Medium,
@@ -476,3 +502,49 @@ impl RknnInitFlags {
         cleared | priority.as_flags()
     }
 }
+
+bitflags! {
+    /// Flags passed to `rknn_create_mem2` controlling how memory is allocated.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct MemAllocFlags: u64 {
+        const CACHEABLE      = RKNN_FLAG_MEMORY_CACHEABLE as u64;
+        const NON_CACHEABLE  = RKNN_FLAG_MEMORY_NON_CACHEABLE as u64;
+        const TRY_ALLOC_SRAM = RKNN_FLAG_MEMORY_TRY_ALLOC_SRAM as u64;
+        const NO_CONTEXT     = RKNN_MEM_FLAG_ALLOC_NO_CONTEXT as u64;
+    }
+}
+
+impl From<MemAllocFlags> for u64 {
+    fn from(flags: MemAllocFlags) -> Self {
+        flags.bits()
+    }
+}
+
+impl From<u64> for MemAllocFlags {
+    fn from(bits: u64) -> Self {
+        Self::from_bits_truncate(bits)
+    }
+}
+
+impl MemAllocFlags {
+    /// Start from the runtime defaults.
+    pub const fn builder() -> Self {
+        Self::empty()
+    }
+
+    pub const fn with_cacheable(self) -> Self {
+        self.union(Self::CACHEABLE)
+    }
+
+    pub const fn with_non_cacheable(self) -> Self {
+        self.union(Self::NON_CACHEABLE)
+    }
+
+    pub const fn with_try_alloc_sram(self) -> Self {
+        self.union(Self::TRY_ALLOC_SRAM)
+    }
+
+    pub const fn with_no_context(self) -> Self {
+        self.union(Self::NO_CONTEXT)
+    }
+}
@@ -3,14 +3,26 @@ use {
         Error, RKNN,
         api::{RKNNAPI, RknnInitFlags},
     },
-    rknpu2_sys::rknn_context,
-    std::{ffi::c_void, ptr},
+    std::{path::Path, sync::Arc},
 };
 
 /// Trait used for linked
+#[derive(Debug, Clone, Copy, Default)]
 pub struct LinkedAPI;
 
 impl RKNNAPI for LinkedAPI {
+    unsafe fn init(
+        &self,
+        context: *mut rknpu2_sys::rknn_context,
+        model: *mut std::ffi::c_void,
+        size: u32,
+        flag: u32,
+        extend: *mut rknpu2_sys::rknn_init_extend,
+    ) -> Result<std::ffi::c_int, crate::Error> {
+        let ret = unsafe { rknpu2_sys::rknn_init(context, model, size, flag, extend) };
+        Ok(ret)
+    }
+
     #[cfg_attr(
         feature = "docs",
         doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
@@ -435,22 +447,27 @@ impl RKNNAPI for LinkedAPI {
 
 impl RKNN<LinkedAPI> {
     pub fn new(model_data: &mut [u8], flags: RknnInitFlags) -> Result<Self, Error> {
-        let mut ctx: rknn_context = 0;
-        let ret = unsafe {
-            rknpu2_sys::rknn_init(
-                &mut ctx as *mut _,
-                model_data.as_mut_ptr() as *mut c_void,
-                model_data.len() as u32,
-                flags.into(),
-                ptr::null_mut(),
-            )
-        };
-        if ret != 0 {
-            return Err(ret.into());
-        }
-        Ok(Self {
-            ctx,
-            api: LinkedAPI,
-        })
+        RKNN::builder(LinkedAPI)
+            .model_buffer(model_data)
+            .flags(flags)
+            .build()
+    }
+
+    /// Memory-map `path` and initialize a context that keeps the mapping alive,
+    /// so `flags` may include [`RknnInitFlags::MODEL_BUFFER_ZERO_COPY`].
+    pub fn from_file<P: AsRef<Path>>(path: P, flags: RknnInitFlags) -> Result<Self, Error> {
+        RKNN::builder(LinkedAPI)
+            .model_file(path)?
+            .flags(flags)
+            .build()
+    }
+
+    /// Initialize a context from a shared buffer that it keeps alive, so `flags`
+    /// may include [`RknnInitFlags::MODEL_BUFFER_ZERO_COPY`].
+    pub fn from_shared(model_data: Arc<[u8]>, flags: RknnInitFlags) -> Result<Self, Error> {
+        RKNN::builder(LinkedAPI)
+            .model_shared(model_data)
+            .flags(flags)
+            .build()
     }
 }
@@ -1,20 +1,44 @@
 use {
     crate::{
-        RKNN,
+        Error, RKNN,
         api::{RKNNAPI, RknnInitFlags},
     },
-    rknpu2_sys::{rknn, rknn_context},
-    std::{
-        ffi::{OsStr, c_void},
-        ptr,
-    },
+    rknpu2_sys::rknn,
+    std::{ffi::OsStr, path::Path, sync::Arc},
 };
 
+/// Backend that loads the runtime library at runtime.
+///
+/// Cloning is cheap: clones share the loaded library, which is unloaded once
+/// the last clone is dropped.
+#[derive(Clone)]
 pub struct RuntimeAPI {
-    inner: rknn,
+    inner: Arc<rknn>,
+}
+
+impl RuntimeAPI {
+    /// Load librknnrt.so or librknnmrt.so from `path`.
+    pub fn load<P: AsRef<OsStr>>(path: P) -> Result<Self, Error> {
+        let inner = unsafe { rknn::new(path.as_ref()) }
+            .map_err(|err| Error::LibraryLoad(err.to_string()))?;
+        Ok(RuntimeAPI {
+            inner: Arc::new(inner),
+        })
+    }
 }
 
 impl RKNNAPI for RuntimeAPI {
+    unsafe fn init(
+        &self,
+        context: *mut rknpu2_sys::rknn_context,
+        model: *mut std::ffi::c_void,
+        size: u32,
+        flag: u32,
+        extend: *mut rknpu2_sys::rknn_init_extend,
+    ) -> Result<std::ffi::c_int, crate::Error> {
+        unsafe { Ok(self.inner.rknn_init(context, model, size, flag, extend)) }
+    }
+
     unsafe fn destroy(
         &self,
         context: rknpu2_sys::rknn_context,
@@ -209,9 +233,9 @@ impl RKNNAPI for RuntimeAPI {
 
     #[cfg_attr(
         feature = "docs",
-        doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+        doc(cfg(any(feature = "rk35xx", feature = "rk3576", feature = "rv110x")))
     )]
-    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    #[cfg(any(feature = "rk35xx", feature = "rk3576", feature = "rv110x"))]
     unsafe fn create_mem_from_fd(
         &self,
         ctx: rknpu2_sys::rknn_context,
@@ -429,23 +453,35 @@ impl RKNN<RuntimeAPI> {
         model_data: &mut [u8],
         flags: RknnInitFlags,
     ) -> Result<Self, crate::Error> {
-        let rknn = unsafe { rknn::new(path).unwrap() };
-        let mut ctx: rknn_context = 0;
-        let ret = unsafe {
-            rknn.rknn_init(
-                &mut ctx as *mut _,
-                model_data.as_mut_ptr() as *mut c_void,
-                model_data.len() as u32,
-                flags.into(),
-                ptr::null_mut(),
-            )
-        };
-        if ret != 0 {
-            return Err(ret.into());
-        }
-        Ok(Self {
-            ctx,
-            api: RuntimeAPI { inner: rknn },
-        })
+        RKNN::builder(RuntimeAPI::load(path)?)
+            .model_buffer(model_data)
+            .flags(flags)
+            .build()
+    }
+
+    /// Memory-map `model_path` and initialize a context that keeps the mapping
+    /// alive, so `flags` may include [`RknnInitFlags::MODEL_BUFFER_ZERO_COPY`].
+    pub fn from_file_with_library<P: AsRef<OsStr>, M: AsRef<Path>>(
+        path: P,
+        model_path: M,
+        flags: RknnInitFlags,
+    ) -> Result<Self, Error> {
+        RKNN::builder(RuntimeAPI::load(path)?)
+            .model_file(model_path)?
+            .flags(flags)
+            .build()
+    }
+
+    /// Initialize a context from a shared buffer that it keeps alive, so `flags`
+    /// may include [`RknnInitFlags::MODEL_BUFFER_ZERO_COPY`].
+    pub fn from_shared_with_library<P: AsRef<OsStr>>(
+        path: P,
+        model_data: Arc<[u8]>,
+        flags: RknnInitFlags,
+    ) -> Result<Self, Error> {
+        RKNN::builder(RuntimeAPI::load(path)?)
+            .model_shared(model_data)
+            .flags(flags)
+            .build()
     }
 }
@@ -0,0 +1,129 @@
+/// async/await front end running a context on a dedicated worker thread.
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+use crate::{
+    io::{
+        buffer::BufMutView,
+        input::OwnedInput,
+        output::{Output, OutputKind},
+    },
+    query::{InputOutputNum, OutputAttr, TensorAttrView},
+};
+use {
+    crate::{Error, RKNN, api::RKNNAPI},
+    futures::{
+        SinkExt, StreamExt,
+        channel::{mpsc, oneshot},
+        executor::block_on,
+        lock::Mutex,
+    },
+    std::thread::JoinHandle,
+};
+
+type Job<A> = Box<dyn FnOnce(&RKNN<A>) + Send>;
+
+/// Handle to an [`RKNN`] owned by a worker thread.
+///
+/// Requests are queued in a bounded channel, so callers wait (asynchronously)
+/// once `queue_depth` requests are pending. The futures do not depend on a
+/// particular executor.
+///
+/// Dropping a future before its request is queued cancels it. Once queued the
+/// request is skipped if its future has been dropped by the time the worker
+/// gets to it; a request that is already running finishes and its result is
+/// discarded.
+pub struct AsyncRknn<A: RKNNAPI + Send + 'static> {
+    sender: Mutex<mpsc::Sender<Job<A>>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl<A: RKNNAPI + Send + 'static> AsyncRknn<A> {
+    /// Move `rknn` to a new worker thread, allowing `queue_depth` pending requests.
+    pub fn new(rknn: RKNN<A>, queue_depth: usize) -> Result<Self, Error> {
+        // The channel holds one message per sender on top of its buffer, so
+        // a single sender is kept behind a lock.
+        let (sender, mut receiver) = mpsc::channel::<Job<A>>(queue_depth.saturating_sub(1));
+        let worker = std::thread::Builder::new()
+            .name("rknn-worker".into())
+            .spawn(move || {
+                while let Some(job) = block_on(receiver.next()) {
+                    job(&rknn);
+                }
+            })?;
+
+        Ok(AsyncRknn {
+            sender: Mutex::new(sender),
+            worker: Some(worker),
+        })
+    }
+
+    /// Run `f` on the worker thread and return its result.
+    pub async fn call<R, F>(&self, f: F) -> Result<R, Error>
+    where
+        R: Send + 'static,
+        F: FnOnce(&RKNN<A>) -> R + Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let job: Job<A> = Box::new(move |rknn| {
+            if tx.is_canceled() {
+                return;
+            }
+            let _ = tx.send(f(rknn));
+        });
+
+        self.sender
+            .lock()
+            .await
+            .send(job)
+            .await
+            .map_err(|_| Error::WorkerStopped)?;
+        rx.await.map_err(|_| Error::WorkerStopped)
+    }
+
+    /// Set `inputs`, run inference and return every output as `f32`.
+    #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+    #[cfg_attr(
+        feature = "docs",
+        doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+    )]
+    pub async fn infer(&self, inputs: Vec<OwnedInput>) -> Result<Vec<Vec<f32>>, Error> {
+        self.call(move |rknn| {
+            rknn.set_inputs(inputs.iter().map(OwnedInput::as_input).collect::<Vec<_>>())?;
+            rknn.run()?;
+
+            let num_outputs = rknn.query::<InputOutputNum>()?.output_num();
+            let mut results = (0..num_outputs)
+                .map(|i| {
+                    let attr = rknn.query_with_input::<OutputAttr>(i)?;
+                    Ok(vec![0.0f32; attr.num_elements() as usize])
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let mut outputs = results
+                .iter_mut()
+                .enumerate()
+                .map(|(i, buf)| Output {
+                    index: i as u32,
+                    kind: OutputKind::Preallocated {
+                        buf: BufMutView::F32(buf),
+                        want_float: true,
+                    },
+                })
+                .collect::<Vec<_>>();
+            rknn.get_outputs(&mut outputs)?;
+            drop(outputs);
+
+            Ok(results)
+        })
+        .await?
+    }
+}
+
+impl<A: RKNNAPI + Send + 'static> Drop for AsyncRknn<A> {
+    fn drop(&mut self) {
+        // Closing the channel lets the worker finish the queue and exit.
+        self.sender.get_mut().close_channel();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,266 @@
+/// Repeated inference timing with summary statistics.
+#[cfg(feature = "rk3576")]
+use crate::rknn::NpuCores;
+use {
+    crate::{
+        Error, RKNN,
+        api::{Priority, RKNNAPI, RknnInitFlags},
+        query::PerfRun,
+    },
+    std::time::Instant,
+};
+
+/// Summary of a set of durations, all in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Stats {
+    pub min: f64,
+    pub mean: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl Stats {
+    /// Compute the statistics of `samples` (in microseconds). Percentiles use
+    /// the nearest-rank method. Returns all zeroes for no samples.
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Stats::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let percentile = |p: f64| {
+            let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+            sorted[rank.clamp(1, sorted.len()) - 1]
+        };
+
+        Stats {
+            min: sorted[0],
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p50: percentile(50.0),
+            p90: percentile(90.0),
+            p99: percentile(99.0),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+
+    /// Frames per second at the mean duration.
+    pub fn fps(&self) -> f64 {
+        if self.mean > 0.0 {
+            1e6 / self.mean
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Timings for one core mask / priority combination.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BenchResult {
+    /// Core mask the context was pinned to, `None` if left unchanged.
+    #[cfg(feature = "rk3576")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "rk3576")))]
+    pub cores: Option<NpuCores>,
+    /// Priority the context was created with, `None` if not set by the bench.
+    pub priority: Option<Priority>,
+    pub iterations: usize,
+    /// Time spent in `rknn_run`, measured on the host.
+    pub wall: Stats,
+    /// `RKNN_QUERY_PERF_RUN` durations, only collected for contexts created
+    /// with [`RknnInitFlags::COLLECT_PERF_MASK`].
+    pub npu: Option<Stats>,
+    /// Frames per second at the mean wall-clock time.
+    pub fps: f64,
+}
+
+/// Results of a [`Bench`] run, one entry per configuration.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BenchReport {
+    pub warmup: usize,
+    pub results: Vec<BenchResult>,
+}
+
+/// Runs a context repeatedly after a warmup and collects timings.
+///
+/// Inputs must be set on the context before benchmarking; every iteration
+/// only calls `rknn_run`.
+#[derive(Debug, Clone)]
+pub struct Bench {
+    warmup: usize,
+    iterations: usize,
+    #[cfg(feature = "rk3576")]
+    cores: Vec<NpuCores>,
+}
+
+impl Bench {
+    /// Time `iterations` runs after 10 warmup runs.
+    pub fn new(iterations: usize) -> Self {
+        Bench {
+            warmup: 10,
+            iterations,
+            #[cfg(feature = "rk3576")]
+            cores: Vec::new(),
+        }
+    }
+
+    /// Number of untimed runs before each measurement.
+    pub fn warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    /// Measure once per core mask. The context is left pinned to the last mask.
+    #[cfg(feature = "rk3576")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "rk3576")))]
+    pub fn cores(mut self, cores: &[NpuCores]) -> Self {
+        self.cores = cores.to_vec();
+        self
+    }
+
+    /// Benchmark `rknn`, once per configured core mask.
+    pub fn run<A: RKNNAPI>(&self, rknn: &RKNN<A>) -> Result<BenchReport, Error> {
+        Ok(BenchReport {
+            warmup: self.warmup,
+            results: self.run_masks(rknn, None)?,
+        })
+    }
+
+    /// Benchmark a context per priority level.
+    ///
+    /// Priority is fixed when the context is created, so `make` is called with
+    /// the flags to create it with and must return a context with its inputs set.
+    pub fn run_priorities<A, F>(
+        &self,
+        priorities: &[Priority],
+        mut make: F,
+    ) -> Result<BenchReport, Error>
+    where
+        A: RKNNAPI,
+        F: FnMut(RknnInitFlags) -> Result<RKNN<A>, Error>,
+    {
+        let mut results = Vec::new();
+        for &priority in priorities {
+            let rknn = make(RknnInitFlags::empty().with_priority(priority))?;
+            results.extend(self.run_masks(&rknn, Some(priority))?);
+        }
+        Ok(BenchReport {
+            warmup: self.warmup,
+            results,
+        })
+    }
+
+    #[cfg(feature = "rk3576")]
+    fn run_masks<A: RKNNAPI>(
+        &self,
+        rknn: &RKNN<A>,
+        priority: Option<Priority>,
+    ) -> Result<Vec<BenchResult>, Error> {
+        if self.cores.is_empty() {
+            return Ok(vec![self.measure(rknn, priority)?]);
+        }
+        self.cores
+            .iter()
+            .map(|&cores| {
+                rknn.set_core_mask(cores)?;
+                let mut result = self.measure(rknn, priority)?;
+                result.cores = Some(cores);
+                Ok(result)
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "rk3576"))]
+    fn run_masks<A: RKNNAPI>(
+        &self,
+        rknn: &RKNN<A>,
+        priority: Option<Priority>,
+    ) -> Result<Vec<BenchResult>, Error> {
+        Ok(vec![self.measure(rknn, priority)?])
+    }
+
+    fn measure<A: RKNNAPI>(
+        &self,
+        rknn: &RKNN<A>,
+        priority: Option<Priority>,
+    ) -> Result<BenchResult, Error> {
+        for _ in 0..self.warmup {
+            rknn.run()?;
+        }
+
+        let collect_perf = rknn.flags.contains(RknnInitFlags::COLLECT_PERF_MASK);
+        let mut wall = Vec::with_capacity(self.iterations);
+        let mut npu = Vec::new();
+        for _ in 0..self.iterations {
+            let start = Instant::now();
+            rknn.run()?;
+            wall.push(start.elapsed().as_secs_f64() * 1e6);
+
+            if collect_perf {
+                npu.push(rknn.query::<PerfRun>()?.run_duration() as f64);
+            }
+        }
+
+        let wall = Stats::from_samples(&wall);
+        Ok(BenchResult {
+            #[cfg(feature = "rk3576")]
+            cores: None,
+            priority,
+            iterations: self.iterations,
+            fps: wall.fps(),
+            wall,
+            npu: collect_perf.then(|| Stats::from_samples(&npu)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_nearest_rank() {
+        let samples = (1..=100).rev().map(f64::from).collect::<Vec<_>>();
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 100.0);
+        assert_eq!(stats.mean, 50.5);
+        assert_eq!(stats.p50, 50.0);
+        assert_eq!(stats.p90, 90.0);
+        assert_eq!(stats.p99, 99.0);
+        assert_eq!(Stats::from_samples(&[2000.0]).fps(), 500.0);
+    }
+
+    #[test]
+    fn stats_empty() {
+        let stats = Stats::from_samples(&[]);
+        assert_eq!(stats, Stats::default());
+        assert_eq!(stats.fps(), 0.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn report_serializes_to_json() {
+        let report = BenchReport {
+            warmup: 1,
+            results: vec![BenchResult {
+                #[cfg(feature = "rk3576")]
+                cores: Some(NpuCores::CORE0),
+                priority: Some(Priority::High),
+                iterations: 1,
+                wall: Stats::from_samples(&[1000.0]),
+                npu: None,
+                fps: 1000.0,
+            }],
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["results"][0]["priority"], "High");
+        assert_eq!(json["results"][0]["wall"]["p99"], 1000.0);
+        assert!(json["results"][0]["npu"].is_null());
+    }
+}
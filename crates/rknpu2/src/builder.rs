@@ -0,0 +1,215 @@
+#[cfg(feature = "rk3576")]
+use {crate::rknn::NpuCores, std::sync::atomic::AtomicU32};
+use {
+    crate::{
+        Error, RKNN,
+        api::{RKNNAPI, RknnInitFlags},
+    },
+    memmap2::{MmapMut, MmapOptions},
+    rknpu2_sys::rknn_init_extend,
+    std::{
+        ffi::{CString, c_void},
+        fs::File,
+        os::{fd::AsRawFd, unix::ffi::OsStrExt},
+        path::Path,
+        ptr,
+        sync::{Arc, Mutex},
+    },
+};
+
+/// Where `rknn_init` reads the model from.
+enum ModelSource<'m> {
+    Buffer(&'m mut [u8]),
+    Path(CString),
+    Owned(ModelStorage),
+}
+
+/// Model memory kept alive for the whole lifetime of a context, so it can be
+/// used with [`RknnInitFlags::MODEL_BUFFER_ZERO_COPY`].
+pub(crate) enum ModelStorage {
+    /// Private copy-on-write mapping of a model file.
+    Mapped(MmapMut),
+    /// Buffer that may be shared with other owners; never written, see
+    /// [`RknnBuilder::model_shared`].
+    Shared(Arc<[u8]>),
+}
+
+impl ModelStorage {
+    fn as_ptr_and_len(&mut self) -> (*mut c_void, usize) {
+        match self {
+            ModelStorage::Mapped(map) => (map.as_mut_ptr() as *mut c_void, map.len()),
+            // `rknn_init` takes a mutable pointer but only reads the model, so
+            // the shared data is never written through it.
+            ModelStorage::Shared(data) => (data.as_ptr() as *mut c_void, data.len()),
+        }
+    }
+}
+
+/// Builder for an [`RKNN`] context, exposing the `rknn_init_extend` options.
+pub struct RknnBuilder<'m, A: RKNNAPI> {
+    api: A,
+    model: Option<ModelSource<'m>>,
+    flags: RknnInitFlags,
+    extend: rknn_init_extend,
+    use_extend: bool,
+    weights: Option<Arc<dyn Send + Sync>>,
+}
+
+impl<'m, A: RKNNAPI> RknnBuilder<'m, A> {
+    pub fn new(api: A) -> Self {
+        RknnBuilder {
+            api,
+            model: None,
+            flags: RknnInitFlags::empty(),
+            extend: rknn_init_extend {
+                ctx: 0,
+                real_model_offset: 0,
+                real_model_size: 0,
+                model_buffer_fd: 0,
+                model_buffer_flags: 0,
+                reserved: [0; 112],
+            },
+            use_extend: false,
+            weights: None,
+        }
+    }
+
+    /// Load the model from memory.
+    ///
+    /// The buffer is only borrowed for the duration of `rknn_init`, so
+    /// [`build`](Self::build) rejects it when combined with
+    /// [`RknnInitFlags::MODEL_BUFFER_ZERO_COPY`]. Use
+    /// [`model_file`](Self::model_file) or [`model_shared`](Self::model_shared) instead.
+    pub fn model_buffer(mut self, model_data: &'m mut [u8]) -> Self {
+        self.model = Some(ModelSource::Buffer(model_data));
+        self
+    }
+
+    /// Memory-map a model file and keep the mapping alive alongside the context.
+    ///
+    /// The mapping is private and copy-on-write; the file must not be
+    /// truncated or modified while the context exists.
+    pub fn model_file<P: AsRef<Path>>(mut self, path: P) -> Result<Self, Error> {
+        let file = File::open(path)?;
+        let map = unsafe { MmapOptions::new().map_copy(&file)? };
+        self.model = Some(ModelSource::Owned(ModelStorage::Mapped(map)));
+        Ok(self)
+    }
+
+    /// Load the model from a shared buffer that is kept alive alongside the context.
+    ///
+    /// The buffer is not copied. `rknn_init` takes a `*mut` pointer to it, so
+    /// this relies on the runtime only reading the model, as librknnrt does;
+    /// an [`RKNNAPI`] backend that writes to the model buffer must not be
+    /// used with shared data.
+    pub fn model_shared(mut self, model_data: Arc<[u8]>) -> Self {
+        self.model = Some(ModelSource::Owned(ModelStorage::Shared(model_data)));
+        self
+    }
+
+    /// Let the runtime open the model file itself.
+    pub fn model_path<P: AsRef<Path>>(mut self, path: P) -> Result<Self, Error> {
+        let path =
+            CString::new(path.as_ref().as_os_str().as_bytes()).map_err(|_| Error::ParamInvalid)?;
+        self.model = Some(ModelSource::Path(path));
+        Ok(self)
+    }
+
+    pub fn flags(mut self, flags: RknnInitFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Load a model embedded at `offset` inside a larger file or buffer
+    /// (`real_model_offset` / `real_model_size`).
+    pub fn model_range(mut self, offset: i32, size: u32) -> Self {
+        self.extend.real_model_offset = offset;
+        self.extend.real_model_size = size;
+        self.use_extend = true;
+        self
+    }
+
+    /// DMA-BUF holding the model buffer (`model_buffer_fd` / `model_buffer_flags`).
+    ///
+    /// The fd only has to stay open until [`build`](Self::build) returns.
+    pub fn model_buffer_fd<F: AsRawFd>(mut self, fd: &'m F, flags: u32) -> Self {
+        self.extend.model_buffer_fd = fd.as_raw_fd();
+        self.extend.model_buffer_flags = flags;
+        self.use_extend = true;
+        self
+    }
+
+    /// Share weights with an existing context.
+    ///
+    /// Sets [`RknnInitFlags::SHARE_WEIGHT_MEM`]. The new context holds a
+    /// reference to `other`, so the weights stay alive until both are dropped.
+    pub fn share_weights_with<B>(mut self, other: &Arc<RKNN<B>>) -> Self
+    where
+        B: RKNNAPI + Send + Sync + 'static,
+    {
+        self.extend.ctx = other.ctx;
+        self.flags |= RknnInitFlags::SHARE_WEIGHT_MEM;
+        self.use_extend = true;
+        self.weights = Some(other.clone());
+        self
+    }
+
+    /// Call `rknn_init` and return the new context.
+    pub fn build(self) -> Result<RKNN<A>, Error> {
+        let RknnBuilder {
+            api,
+            mut model,
+            flags,
+            mut extend,
+            use_extend,
+            weights,
+        } = self;
+
+        let (model_ptr, size) = match model.as_mut() {
+            Some(ModelSource::Buffer(buf)) => {
+                let size = u32::try_from(buf.len()).map_err(|_| Error::ParamInvalid)?;
+                (buf.as_mut_ptr() as *mut c_void, size)
+            }
+            // A size of 0 tells the runtime the pointer is a file path.
+            Some(ModelSource::Path(path)) => (path.as_ptr() as *mut c_void, 0),
+            Some(ModelSource::Owned(storage)) => {
+                let (ptr, len) = storage.as_ptr_and_len();
+                let size = u32::try_from(len).map_err(|_| Error::ParamInvalid)?;
+                (ptr, size)
+            }
+            None => return Err(Error::ParamInvalid),
+        };
+        if flags.contains(RknnInitFlags::MODEL_BUFFER_ZERO_COPY)
+            && matches!(model, Some(ModelSource::Buffer(_)))
+        {
+            return Err(Error::ParamInvalid);
+        }
+        let extend_ptr = if use_extend {
+            &mut extend as *mut rknn_init_extend
+        } else {
+            ptr::null_mut()
+        };
+
+        let mut ctx = 0;
+        let ret = unsafe { api.init(&mut ctx, model_ptr, size, flags.into(), extend_ptr)? };
+        if ret != 0 {
+            return Err(ret.into());
+        }
+        let model = match model {
+            Some(ModelSource::Owned(storage)) => Some(Arc::new(storage)),
+            _ => None,
+        };
+        Ok(RKNN {
+            ctx,
+            api,
+            flags,
+            model,
+            weights,
+            bound_mems: Mutex::default(),
+            #[cfg(feature = "rk3576")]
+            core_mask: AtomicU32::new(NpuCores::auto().bits()),
+            #[cfg(feature = "rk3576")]
+            batch_core_num: AtomicU32::new(1),
+        })
+    }
+}
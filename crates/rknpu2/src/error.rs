@@ -37,6 +37,40 @@ pub enum Error {
         expected: usize,
         actual: usize,
     },
+    /// Model file could not be parsed
+    ModelFileInvalid(String),
+    /// Custom string embedded in the model could not be parsed
+    CustomStringInvalid(String),
+    /// I/O error
+    Io(std::io::Error),
+    /// Runtime library could not be loaded
+    LibraryLoad(String),
+    /// Inference did not finish within the timeout given in
+    /// [`RunOptions`](crate::rknn::RunOptions)
+    RunTimeout {
+        frame_id: u64,
+    },
+    /// Outputs for the requested frame were already consumed
+    FrameUnavailable {
+        requested: u64,
+        received: u64,
+    },
+    /// The worker thread owning the context has stopped
+    WorkerStopped,
+    /// A dynamic-shape model does not support the requested input shape,
+    /// alone or combined with the shapes requested for the other inputs
+    UnsupportedInputShape {
+        index: u32,
+        requested: Vec<u32>,
+        supported: Vec<Vec<u32>>,
+    },
+    /// Tensor memory is not aligned for the requested element type
+    Misaligned {
+        align: usize,
+        address: usize,
+    },
+    /// Tensor memory has no CPU mapping
+    Unmapped,
 }
 
 impl std::error::Error for Error {}
@@ -75,10 +109,47 @@ impl std::fmt::Display for Error {
             Error::SizeMismatch { expected, actual } => {
                 write!(f, "Size mismatch: expected {}, actual {}", expected, actual)
             }
+            Error::ModelFileInvalid(reason) => write!(f, "Invalid model file: {}", reason),
+            Error::CustomStringInvalid(reason) => write!(f, "Invalid custom string: {}", reason),
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::LibraryLoad(reason) => write!(f, "Failed to load RKNN library: {}", reason),
+            Error::RunTimeout { frame_id } => {
+                write!(f, "Inference of frame {} timed out", frame_id)
+            }
+            Error::FrameUnavailable {
+                requested,
+                received,
+            } => write!(
+                f,
+                "Outputs for frame {} are no longer available, received frame {}",
+                requested, received
+            ),
+            Error::WorkerStopped => write!(f, "RKNN worker thread has stopped"),
+            Error::UnsupportedInputShape {
+                index,
+                requested,
+                supported,
+            } => write!(
+                f,
+                "Input {} does not support shape {:?}, supported shapes: {:?}",
+                index, requested, supported
+            ),
+            Error::Misaligned { align, address } => write!(
+                f,
+                "Memory at {:#x} is not aligned to {} bytes",
+                address, align
+            ),
+            Error::Unmapped => write!(f, "Memory has no CPU mapping"),
         }
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
 impl From<std::ffi::c_int> for Error {
     fn from(err: std::ffi::c_int) -> Self {
         match err {
@@ -1,3 +1,12 @@
 pub mod buffer;
 pub mod input;
+pub mod mem;
 pub mod output;
+pub mod zero_copy;
+
+#[cfg(any(feature = "rk35xx", feature = "rk3576", feature = "rv110x"))]
+#[cfg_attr(
+    feature = "docs",
+    doc(cfg(any(feature = "rk35xx", feature = "rk3576", feature = "rv110x")))
+)]
+pub mod dma_buf;
@@ -155,6 +155,36 @@ impl<'a> BufView<'a> {
     }
 }
 
+/// Owned data, e.g. for moving inputs to another thread.
+#[derive(Debug, Clone)]
+pub enum Buf {
+    F32(Vec<f32>),
+    I32(Vec<i32>),
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+    I64(Vec<i64>),
+    F16(Vec<f16>),
+    BF16(Vec<bf16>),
+    I8(Vec<i8>),
+}
+
+impl Buf {
+    pub fn as_view(&self) -> BufView<'_> {
+        match self {
+            Buf::F32(data) => BufView::F32(data),
+            Buf::I32(data) => BufView::I32(data),
+            Buf::U8(data) => BufView::U8(data),
+            Buf::U16(data) => BufView::U16(data),
+            Buf::U32(data) => BufView::U32(data),
+            Buf::I64(data) => BufView::I64(data),
+            Buf::F16(data) => BufView::F16(data),
+            Buf::BF16(data) => BufView::BF16(data),
+            Buf::I8(data) => BufView::I8(data),
+        }
+    }
+}
+
 pub struct RknnBuffer {
     pub(crate) ptr: *mut c_void,
     pub(crate) size: usize,
@@ -0,0 +1,175 @@
+/// Importing external DMA-BUF buffers as tensor memory.
+use {
+    crate::{
+        Error, RKNN,
+        api::RKNNAPI,
+        io::mem::{MemSyncMode, TensorMem, checked_parts},
+        tensor::TensorType,
+    },
+    memmap2::{MmapMut, MmapOptions},
+    rknpu2_sys::rknn_tensor_mem,
+    std::{
+        ffi::c_void,
+        marker::PhantomData,
+        os::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd},
+        ptr,
+    },
+};
+
+/// A DMA-BUF, e.g. a V4L2, RGA or MPP frame, imported with
+/// `rknn_create_mem_from_fd` so the NPU reads it without a CPU copy.
+///
+/// Freed with `rknn_destroy_mem` when dropped, before the mapping and any
+/// owned fd are released. The underlying [`TensorMem`] is not exposed: its
+/// CPU address points `offset` bytes before the mapping, and it must not
+/// outlive the mapping or the fd.
+///
+/// ```compile_fail
+/// # use rknpu2::{api::RKNNAPI, io::dma_buf::DmaBufMem};
+/// fn virt_addr<A: RKNNAPI>(mem: &DmaBufMem<'_, '_, A>) {
+///     let _ = mem.virt_addr();
+/// }
+/// ```
+pub struct DmaBufMem<'ctx, 'fd, A: RKNNAPI> {
+    mem: TensorMem<'ctx, A>,
+    map: Option<MmapMut>,
+    owned: Option<OwnedFd>,
+    _fd: PhantomData<BorrowedFd<'fd>>,
+}
+
+impl<'ctx, 'fd, A: RKNNAPI> DmaBufMem<'ctx, 'fd, A> {
+    /// Import `size` bytes at `offset` in `fd` without a CPU mapping.
+    pub fn import(
+        rknn: &'ctx RKNN<A>,
+        fd: BorrowedFd<'fd>,
+        offset: u32,
+        size: u32,
+    ) -> Result<Self, Error> {
+        let mem = create(rknn, fd, ptr::null_mut(), offset, size)?;
+        Ok(DmaBufMem {
+            mem,
+            map: None,
+            owned: None,
+            _fd: PhantomData,
+        })
+    }
+
+    /// Import like [`import`](Self::import) and map the buffer for
+    /// [`as_slice`](Self::as_slice) and [`as_mut_slice`](Self::as_mut_slice).
+    pub fn import_mapped(
+        rknn: &'ctx RKNN<A>,
+        fd: BorrowedFd<'fd>,
+        offset: u32,
+        size: u32,
+    ) -> Result<Self, Error> {
+        let mut map = unsafe {
+            MmapOptions::new()
+                .offset(offset as u64)
+                .len(size as usize)
+                .map_mut(fd.as_raw_fd())?
+        };
+        // The runtime expects the address of the start of the fd, without `offset`.
+        let virt_addr = map.as_mut_ptr().wrapping_sub(offset as usize) as *mut c_void;
+        let mem = create(rknn, fd, virt_addr, offset, size)?;
+        Ok(DmaBufMem {
+            mem,
+            map: Some(map),
+            owned: None,
+            _fd: PhantomData,
+        })
+    }
+
+    /// Pointer to pass to the runtime, e.g. to `rknn_set_io_mem`.
+    pub fn as_ptr(&self) -> *mut rknn_tensor_mem {
+        self.mem.as_ptr()
+    }
+
+    /// DMA-BUF fd the memory was imported from.
+    pub fn fd(&self) -> i32 {
+        self.mem.fd()
+    }
+
+    /// Offset of the memory within its fd.
+    pub fn offset(&self) -> i32 {
+        self.mem.offset()
+    }
+
+    /// Size in bytes.
+    pub fn size(&self) -> usize {
+        self.mem.size()
+    }
+
+    /// Synchronize the CPU cache of the buffer with the device.
+    pub fn sync(&self, mode: MemSyncMode) -> Result<(), Error> {
+        self.mem.sync(mode)
+    }
+
+    /// Whether the buffer was mapped by [`import_mapped`](Self::import_mapped).
+    pub fn is_mapped(&self) -> bool {
+        self.map.is_some()
+    }
+
+    /// View the mapped buffer as elements of `T`, with the checks of
+    /// [`TensorMem::as_slice`].
+    pub fn as_slice<T: TensorType>(&self) -> Result<&[T], Error> {
+        let map = self.map.as_ref().ok_or(Error::Unmapped)?;
+        let (ptr, len) = checked_parts::<T>(map.as_ptr() as *mut c_void, map.len())?;
+        Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+    }
+
+    /// Mutable view of the mapped buffer as elements of `T`.
+    pub fn as_mut_slice<T: TensorType>(&mut self) -> Result<&mut [T], Error> {
+        let map = self.map.as_mut().ok_or(Error::Unmapped)?;
+        let (ptr, len) = checked_parts::<T>(map.as_mut_ptr() as *mut c_void, map.len())?;
+        Ok(unsafe { std::slice::from_raw_parts_mut(ptr, len) })
+    }
+}
+
+impl<'ctx, A: RKNNAPI> DmaBufMem<'ctx, 'static, A> {
+    /// Import like [`import`](Self::import), keeping `fd` open until the
+    /// memory is dropped.
+    pub fn from_owned_fd(
+        rknn: &'ctx RKNN<A>,
+        fd: OwnedFd,
+        offset: u32,
+        size: u32,
+    ) -> Result<Self, Error> {
+        let mem = create(rknn, fd.as_fd(), ptr::null_mut(), offset, size)?;
+        Ok(DmaBufMem {
+            mem,
+            map: None,
+            owned: Some(fd),
+            _fd: PhantomData,
+        })
+    }
+
+    /// Take back the fd passed to [`from_owned_fd`](Self::from_owned_fd),
+    /// after freeing the imported memory.
+    pub fn into_fd(mut self) -> Option<OwnedFd> {
+        self.owned.take()
+    }
+}
+
+fn create<'ctx, A: RKNNAPI>(
+    rknn: &'ctx RKNN<A>,
+    fd: BorrowedFd<'_>,
+    virt_addr: *mut c_void,
+    offset: u32,
+    size: u32,
+) -> Result<TensorMem<'ctx, A>, Error> {
+    let offset = i32::try_from(offset).map_err(|_| Error::ParamInvalid)?;
+    let mem = unsafe {
+        rknn.api
+            .create_mem_from_fd(rknn.ctx, fd.as_raw_fd(), virt_addr, size, offset)?
+    };
+    TensorMem::from_raw(rknn, mem)
+}
+
+impl<A: RKNNAPI> std::fmt::Debug for DmaBufMem<'_, '_, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DmaBufMem")
+            .field("mem", &self.mem)
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
+}
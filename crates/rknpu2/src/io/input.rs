@@ -1,5 +1,8 @@
 use {
-    crate::{io::buffer::BufView, tensor::TensorFormatKind},
+    crate::{
+        io::buffer::{Buf, BufView},
+        tensor::TensorFormatKind,
+    },
     rknpu2_sys::rknn_input,
 };
 
@@ -33,6 +36,35 @@ impl<'a> Input<'a> {
     }
 }
 
+/// An input that owns its data.
+#[derive(Debug, Clone)]
+pub struct OwnedInput {
+    pub index: u32,
+    pub buffer: Buf,
+    pub pass_through: bool,
+    pub fmt: TensorFormatKind,
+}
+
+impl OwnedInput {
+    pub fn new(index: u32, buffer: Buf, pass_through: bool, fmt: TensorFormatKind) -> Self {
+        OwnedInput {
+            index,
+            buffer,
+            pass_through,
+            fmt,
+        }
+    }
+
+    pub fn as_input(&self) -> Input<'_> {
+        Input::new(
+            self.index,
+            self.buffer.as_view(),
+            self.pass_through,
+            self.fmt,
+        )
+    }
+}
+
 pub type Inputs<'a> = Vec<Input<'a>>;
 
 pub trait IntoInputs<'a> {
@@ -0,0 +1,185 @@
+/// Runtime-allocated tensor memory.
+use {
+    crate::{
+        Error, RKNN,
+        api::{MemAllocFlags, RKNNAPI},
+        tensor::TensorType,
+    },
+    rknpu2_sys::{_rknn_mem_sync_mode, rknn_tensor_mem},
+    std::{ffi::c_void, mem, ptr::NonNull},
+};
+
+/// Direction of a cache synchronization with [`TensorMem::sync`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemSyncMode {
+    /// Flush CPU writes so the NPU sees them.
+    ToDevice,
+    /// Invalidate the CPU cache so NPU writes become visible.
+    FromDevice,
+    Bidirectional,
+}
+
+impl From<MemSyncMode> for u32 {
+    fn from(mode: MemSyncMode) -> Self {
+        match mode {
+            MemSyncMode::ToDevice => _rknn_mem_sync_mode::RKNN_MEMORY_SYNC_TO_DEVICE,
+            MemSyncMode::FromDevice => _rknn_mem_sync_mode::RKNN_MEMORY_SYNC_FROM_DEVICE,
+            MemSyncMode::Bidirectional => _rknn_mem_sync_mode::RKNN_MEMORY_SYNC_BIDIRECTIONAL,
+        }
+    }
+}
+
+/// Memory allocated by the runtime for a context, freed with
+/// `rknn_destroy_mem` when dropped.
+pub struct TensorMem<'ctx, A: RKNNAPI> {
+    rknn: &'ctx RKNN<A>,
+    mem: NonNull<rknn_tensor_mem>,
+}
+
+impl<'ctx, A: RKNNAPI> TensorMem<'ctx, A> {
+    /// Allocate `size` bytes with `rknn_create_mem`.
+    pub fn new(rknn: &'ctx RKNN<A>, size: u32) -> Result<Self, Error> {
+        let mem = unsafe { rknn.api.create_mem(rknn.ctx, size)? };
+        Self::from_raw(rknn, mem)
+    }
+
+    /// Allocate `size` bytes with `rknn_create_mem2`.
+    pub fn with_flags(rknn: &'ctx RKNN<A>, size: u64, flags: MemAllocFlags) -> Result<Self, Error> {
+        let mem = unsafe { rknn.api.create_mem2(rknn.ctx, size, flags.into())? };
+        Self::from_raw(rknn, mem)
+    }
+
+    pub(crate) fn from_raw(rknn: &'ctx RKNN<A>, mem: *mut rknn_tensor_mem) -> Result<Self, Error> {
+        let mem = NonNull::new(mem).ok_or(Error::MallocFailed)?;
+        Ok(TensorMem { rknn, mem })
+    }
+
+    /// Give up ownership without destroying the memory, for memory the
+    /// context still references.
+    pub(crate) fn into_bound(self) -> BoundMem {
+        let mem = BoundMem(self.mem);
+        mem::forget(self);
+        mem
+    }
+
+    fn raw(&self) -> &rknn_tensor_mem {
+        unsafe { self.mem.as_ref() }
+    }
+
+    /// Pointer to pass to the runtime, e.g. to `rknn_set_io_mem`.
+    pub fn as_ptr(&self) -> *mut rknn_tensor_mem {
+        self.mem.as_ptr()
+    }
+
+    /// CPU address of the memory, null if it is not mapped.
+    pub fn virt_addr(&self) -> *mut c_void {
+        self.raw().virt_addr
+    }
+
+    pub fn phys_addr(&self) -> u64 {
+        self.raw().phys_addr
+    }
+
+    /// DMA-BUF fd backing the memory.
+    pub fn fd(&self) -> i32 {
+        self.raw().fd
+    }
+
+    /// Offset of the memory within its fd.
+    pub fn offset(&self) -> i32 {
+        self.raw().offset
+    }
+
+    /// Size in bytes.
+    pub fn size(&self) -> usize {
+        self.raw().size as usize
+    }
+
+    /// View the memory as elements of `T`.
+    ///
+    /// Fails with [`Error::Unmapped`] if the memory has no CPU address,
+    /// [`Error::Misaligned`] if it is not aligned for `T` and
+    /// [`Error::SizeMismatch`] if its size is not a multiple of `T`.
+    pub fn as_slice<T: TensorType>(&self) -> Result<&[T], Error> {
+        let (ptr, len) = checked_parts::<T>(self.virt_addr(), self.size())?;
+        Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+    }
+
+    /// Mutable view of the memory as elements of `T`, with the checks of
+    /// [`as_slice`](Self::as_slice).
+    pub fn as_mut_slice<T: TensorType>(&mut self) -> Result<&mut [T], Error> {
+        let (ptr, len) = checked_parts::<T>(self.virt_addr(), self.size())?;
+        Ok(unsafe { std::slice::from_raw_parts_mut(ptr, len) })
+    }
+
+    /// Synchronize the CPU cache of cacheable memory with the device.
+    pub fn sync(&self, mode: MemSyncMode) -> Result<(), Error> {
+        let ret = unsafe {
+            self.rknn
+                .api
+                .mem_sync(self.rknn.ctx, self.as_ptr(), mode.into())?
+        };
+        if ret != 0 {
+            return Err(ret.into());
+        }
+        Ok(())
+    }
+}
+
+/// Memory left bound to a context with `rknn_set_io_mem`, destroyed right
+/// before the context.
+pub(crate) struct BoundMem(pub(crate) NonNull<rknn_tensor_mem>);
+
+// SAFETY: the pointer is only handed back to the runtime that allocated it.
+unsafe impl Send for BoundMem {}
+
+impl BoundMem {
+    /// Free the memory with `rknn_destroy_mem` on the context it was
+    /// allocated for.
+    pub(crate) fn destroy<A: RKNNAPI>(self, rknn: &RKNN<A>) {
+        unsafe {
+            let _ = rknn.api.destroy_mem(rknn.ctx, self.0.as_ptr());
+        }
+    }
+}
+
+/// Pointer and length of `size` bytes at `addr` viewed as `T`.
+pub(crate) fn checked_parts<T>(addr: *mut c_void, size: usize) -> Result<(*mut T, usize), Error> {
+    let ptr = addr as *mut T;
+    if ptr.is_null() {
+        return Err(Error::Unmapped);
+    }
+    if !ptr.is_aligned() {
+        return Err(Error::Misaligned {
+            align: mem::align_of::<T>(),
+            address: ptr as usize,
+        });
+    }
+    if !size.is_multiple_of(mem::size_of::<T>()) {
+        return Err(Error::SizeMismatch {
+            expected: size.next_multiple_of(mem::size_of::<T>()),
+            actual: size,
+        });
+    }
+    Ok((ptr, size / mem::size_of::<T>()))
+}
+
+impl<A: RKNNAPI> std::fmt::Debug for TensorMem<'_, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TensorMem")
+            .field("virt_addr", &self.virt_addr())
+            .field("phys_addr", &self.phys_addr())
+            .field("fd", &self.fd())
+            .field("offset", &self.offset())
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+impl<A: RKNNAPI> Drop for TensorMem<'_, A> {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.rknn.api.destroy_mem(self.rknn.ctx, self.as_ptr());
+        }
+    }
+}
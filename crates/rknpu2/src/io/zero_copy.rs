@@ -0,0 +1,172 @@
+/// Zero-copy inference through memory bound with `rknn_set_io_mem`.
+use {
+    crate::{
+        Error, RKNN,
+        api::RKNNAPI,
+        io::mem::{MemSyncMode, TensorMem},
+        query::{
+            InputOutputNum, NativeInputAttr, NativeNHWCOutputAttr, TensorAttr, TensorAttrView,
+        },
+        tensor::TensorType,
+    },
+    rknpu2_sys::_rknn_tensor_type,
+};
+
+/// Inputs and outputs allocated by the runtime and bound to a context once.
+///
+/// Inputs use the native layout from `RKNN_QUERY_NATIVE_INPUT_ATTR` and
+/// outputs the NHWC layout from `RKNN_QUERY_NATIVE_NHWC_OUTPUT_ATTR`, each
+/// sized with `size_with_stride`. Write inputs with
+/// [`input_mut`](Self::input_mut), call [`run`](Self::run) and read outputs
+/// with [`output`](Self::output); no data is copied by the runtime.
+///
+/// The session borrows the context exclusively, so nothing else can run it
+/// while outputs are being read. The context keeps using the bound memory
+/// after the session is dropped, so the memory is only destroyed when a
+/// later session rebinds the same tensor or the context is dropped.
+///
+/// This is the I/O path on `rv110x`, where `set_inputs` and `get_outputs`
+/// are not available.
+pub struct ZeroCopySession<'ctx, A: RKNNAPI> {
+    rknn: &'ctx RKNN<A>,
+    input_attrs: Vec<TensorAttr>,
+    output_attrs: Vec<TensorAttr>,
+    input_mems: Vec<TensorMem<'ctx, A>>,
+    output_mems: Vec<TensorMem<'ctx, A>>,
+}
+
+impl<'ctx, A: RKNNAPI> ZeroCopySession<'ctx, A> {
+    /// Allocate memory for every input and output of `rknn` and bind it.
+    pub fn new(rknn: &'ctx mut RKNN<A>) -> Result<Self, Error> {
+        let rknn: &'ctx RKNN<A> = rknn;
+        let num = rknn.query::<InputOutputNum>()?;
+        let input_attrs = (0..num.input_num())
+            .map(|i| rknn.query_with_input::<NativeInputAttr>(i).map(Into::into))
+            .collect::<Result<Vec<TensorAttr>, _>>()?;
+        let output_attrs = (0..num.output_num())
+            .map(|i| {
+                rknn.query_with_input::<NativeNHWCOutputAttr>(i)
+                    .map(Into::into)
+            })
+            .collect::<Result<Vec<TensorAttr>, _>>()?;
+
+        // Memory bound before a failure is handed to the context on drop.
+        let mut session = ZeroCopySession {
+            rknn,
+            input_attrs,
+            output_attrs,
+            input_mems: Vec::new(),
+            output_mems: Vec::new(),
+        };
+        for attr in &session.input_attrs {
+            session.input_mems.push(Self::bind(rknn, attr)?);
+        }
+        for attr in &session.output_attrs {
+            session.output_mems.push(Self::bind(rknn, attr)?);
+        }
+        Ok(session)
+    }
+
+    fn bind(rknn: &'ctx RKNN<A>, attr: &TensorAttr) -> Result<TensorMem<'ctx, A>, Error> {
+        let mem = TensorMem::new(rknn, attr.size_with_stride())?;
+        let mut sys_attr = attr.to_sys();
+        let ret = unsafe { rknn.api.set_io_mem(rknn.ctx, mem.as_ptr(), &mut sys_attr)? };
+        if ret != 0 {
+            return Err(ret.into());
+        }
+
+        // Memory a previous session left bound to this tensor is no longer used.
+        let replaced = rknn
+            .bound_mems
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&(attr.io(), attr.index()));
+        if let Some(replaced) = replaced {
+            replaced.destroy(rknn);
+        }
+        Ok(mem)
+    }
+
+    /// Attributes the inputs were bound with.
+    pub fn input_attrs(&self) -> &[TensorAttr] {
+        &self.input_attrs
+    }
+
+    /// Attributes the outputs were bound with.
+    pub fn output_attrs(&self) -> &[TensorAttr] {
+        &self.output_attrs
+    }
+
+    /// Memory bound to input `index`, e.g. to pass its fd to another device.
+    pub fn input_mem(&self, index: usize) -> Option<&TensorMem<'ctx, A>> {
+        self.input_mems.get(index)
+    }
+
+    /// Memory bound to output `index`.
+    pub fn output_mem(&self, index: usize) -> Option<&TensorMem<'ctx, A>> {
+        self.output_mems.get(index)
+    }
+
+    /// Writable view of input `index`, including any stride padding.
+    ///
+    /// Fails with [`Error::InputInvalid`] for an unknown index and
+    /// [`Error::TensorTypeMismatch`] if `T` is not the input's native type.
+    pub fn input_mut<T: TensorType>(&mut self, index: usize) -> Result<&mut [T], Error> {
+        let attr = self.input_attrs.get(index).ok_or(Error::InputInvalid)?;
+        check_type::<T>(attr)?;
+        self.input_mems[index].as_mut_slice()
+    }
+
+    /// View of output `index` as written by the last [`run`](Self::run),
+    /// including any stride padding.
+    ///
+    /// Fails with [`Error::OutputInvalid`] for an unknown index and
+    /// [`Error::TensorTypeMismatch`] if `T` is not the output's native type.
+    pub fn output<T: TensorType>(&self, index: usize) -> Result<&[T], Error> {
+        let attr = self.output_attrs.get(index).ok_or(Error::OutputInvalid)?;
+        check_type::<T>(attr)?;
+        self.output_mems[index].as_slice()
+    }
+
+    /// Run inference on the bound inputs.
+    ///
+    /// Inputs are synced to the device before the run and outputs back from
+    /// it afterwards, so cacheable memory needs no extra
+    /// [`sync`](TensorMem::sync).
+    pub fn run(&mut self) -> Result<(), Error> {
+        for mem in &self.input_mems {
+            mem.sync(MemSyncMode::ToDevice)?;
+        }
+        self.rknn.run()?;
+        for mem in &self.output_mems {
+            mem.sync(MemSyncMode::FromDevice)?;
+        }
+        Ok(())
+    }
+}
+
+impl<A: RKNNAPI> Drop for ZeroCopySession<'_, A> {
+    fn drop(&mut self) {
+        let mut bound_mems = self
+            .rknn
+            .bound_mems
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let attrs = self.input_attrs.iter().chain(&self.output_attrs);
+        let mems = self.input_mems.drain(..).chain(self.output_mems.drain(..));
+        for (attr, mem) in attrs.zip(mems) {
+            bound_mems.insert((attr.io(), attr.index()), mem.into_bound());
+        }
+    }
+}
+
+fn check_type<T: TensorType>(attr: &TensorAttr) -> Result<(), Error> {
+    let expected: _rknn_tensor_type::Type = attr.dtype().into();
+    if expected != T::TYPE {
+        return Err(Error::TensorTypeMismatch {
+            expected,
+            actual: T::TYPE,
+        });
+    }
+    Ok(())
+}
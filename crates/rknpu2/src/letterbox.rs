@@ -0,0 +1,150 @@
+/// Fitting images into the input shapes of a dynamic-shape model.
+use crate::tensor::TensorFormatKind;
+
+/// How an image is scaled and padded into a model input, keeping its aspect
+/// ratio.
+///
+/// Resize the image to `resized_width` x `resized_height`, paste it at
+/// (`pad_x`, `pad_y`) on a `width` x `height` canvas and convert it with
+/// `rktensor::to_tensor`. Results in model coordinates are mapped back with
+/// [`to_image`](Self::to_image).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Letterbox {
+    /// Model input width.
+    pub width: u32,
+    /// Model input height.
+    pub height: u32,
+    /// Factor the image is scaled by.
+    pub scale: f32,
+    pub resized_width: u32,
+    pub resized_height: u32,
+    /// Padding on the left; the right gets the remainder.
+    pub pad_x: u32,
+    /// Padding on the top; the bottom gets the remainder.
+    pub pad_y: u32,
+}
+
+impl Letterbox {
+    /// Fit an `image_width` x `image_height` image into `width` x `height`,
+    /// centered.
+    pub fn new(image_width: u32, image_height: u32, width: u32, height: u32) -> Self {
+        let scale = (width as f32 / image_width.max(1) as f32)
+            .min(height as f32 / image_height.max(1) as f32);
+        let resized_width = ((image_width as f32 * scale).round() as u32).min(width);
+        let resized_height = ((image_height as f32 * scale).round() as u32).min(height);
+
+        Letterbox {
+            width,
+            height,
+            scale,
+            resized_width,
+            resized_height,
+            pad_x: (width - resized_width) / 2,
+            pad_y: (height - resized_height) / 2,
+        }
+    }
+
+    /// Map a point in model input coordinates to the original image.
+    pub fn to_image(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (x - self.pad_x as f32) / self.scale,
+            (y - self.pad_y as f32) / self.scale,
+        )
+    }
+
+    /// Map a point in the original image to model input coordinates.
+    pub fn to_model(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            x * self.scale + self.pad_x as f32,
+            y * self.scale + self.pad_y as f32,
+        )
+    }
+}
+
+/// Width and height of a 4-D image shape in `fmt`.
+pub(crate) fn image_size(dims: &[u32], fmt: TensorFormatKind) -> Option<(u32, u32)> {
+    match (fmt, dims) {
+        (TensorFormatKind::NHWC(_), &[_, h, w, _]) => Some((w, h)),
+        (TensorFormatKind::NCHW(_), &[_, _, h, w]) => Some((w, h)),
+        _ => None,
+    }
+}
+
+/// Index of the smallest shape that holds `width` x `height` without
+/// downscaling, or else of the shape with the closest aspect ratio (the
+/// largest one on ties).
+pub(crate) fn choose_shape(
+    shapes: &[Vec<u32>],
+    fmt: TensorFormatKind,
+    width: u32,
+    height: u32,
+) -> Option<usize> {
+    let sizes = shapes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, dims)| image_size(dims, fmt).map(|size| (i, size)))
+        .collect::<Vec<_>>();
+    let area = |(w, h): (u32, u32)| w as u64 * h as u64;
+
+    let fitting = sizes
+        .iter()
+        .filter(|(_, (w, h))| *w >= width && *h >= height)
+        .min_by_key(|(_, size)| area(*size));
+    if let Some((i, _)) = fitting {
+        return Some(*i);
+    }
+
+    let aspect = (width.max(1) as f64 / height.max(1) as f64).ln();
+    let distance = |(w, h): (u32, u32)| (((w.max(1) as f64) / h.max(1) as f64).ln() - aspect).abs();
+    sizes
+        .iter()
+        .min_by(|(_, a), (_, b)| {
+            distance(*a)
+                .total_cmp(&distance(*b))
+                .then_with(|| area(*b).cmp(&area(*a)))
+        })
+        .map(|(i, _)| *i)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::tensor::TensorFormat};
+
+    const NHWC: TensorFormatKind = TensorFormatKind::NHWC(TensorFormat::NHWC);
+
+    #[test]
+    fn letterbox_centers_and_maps_back() {
+        let lb = Letterbox::new(1280, 720, 640, 640);
+        assert_eq!(lb.scale, 0.5);
+        assert_eq!((lb.resized_width, lb.resized_height), (640, 360));
+        assert_eq!((lb.pad_x, lb.pad_y), (0, 140));
+
+        assert_eq!(lb.to_model(1280.0, 720.0), (640.0, 500.0));
+        assert_eq!(lb.to_image(320.0, 320.0), (640.0, 360.0));
+    }
+
+    #[test]
+    fn picks_smallest_fitting_shape() {
+        let shapes = vec![
+            vec![1, 640, 640, 3],
+            vec![1, 320, 320, 3],
+            vec![1, 480, 640, 3],
+        ];
+        assert_eq!(choose_shape(&shapes, NHWC, 300, 200), Some(1));
+        assert_eq!(choose_shape(&shapes, NHWC, 600, 400), Some(2));
+        assert_eq!(choose_shape(&shapes, NHWC, 640, 600), Some(0));
+    }
+
+    #[test]
+    fn falls_back_to_closest_aspect_ratio() {
+        let shapes = vec![
+            vec![1, 3, 320, 320],
+            vec![1, 3, 360, 640],
+            vec![1, 3, 180, 320],
+        ];
+        let nchw = TensorFormatKind::NCHW(TensorFormat::NCHW);
+        assert_eq!(choose_shape(&shapes, nchw, 1920, 1080), Some(1));
+        assert_eq!(choose_shape(&shapes, nchw, 1000, 1000), Some(0));
+        assert_eq!(choose_shape(&[vec![1, 1000]], nchw, 10, 10), None);
+    }
+}
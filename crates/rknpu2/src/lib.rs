@@ -10,6 +10,38 @@ pub mod query;
 /// Main RKNN struct
 pub mod rknn;
 
+/// Offline model file parser
+pub mod model;
+
+/// Context builder
+pub mod builder;
+
+/// Multi-core context pool
+#[cfg(feature = "rk3576")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "rk3576")))]
+pub mod pool;
+
+/// Fitting images into dynamic input shapes
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[cfg_attr(
+    feature = "docs",
+    doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+)]
+pub mod letterbox;
+
+/// Inference benchmarks
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[cfg_attr(
+    feature = "docs",
+    doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+)]
+pub mod bench;
+
+/// async/await inference on a worker thread
+#[cfg(feature = "async")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "async")))]
+pub mod async_rknn;
+
 /// Input and output types
 pub mod io;
 
@@ -0,0 +1,324 @@
+/// Offline `.rknn` model file parser.
+///
+/// Reads the metadata the toolkit embeds in a model file without loading
+/// librknnrt, so models can be inspected on a host machine.
+use {
+    crate::{Error, query::Io},
+    std::path::Path,
+};
+
+pub mod compat;
+mod flatbuffer;
+pub mod value;
+
+pub use {
+    compat::{CompatibilityIssue, CompatibilityReport, Platform, check_compatibility},
+    value::Value,
+};
+
+use flatbuffer::Table;
+
+/// Magic bytes at the start of every model file.
+pub const MAGIC: &[u8; 4] = b"RKNN";
+
+/// Size of the fixed file header preceding the model section.
+pub const HEADER_SIZE: usize = 64;
+
+/// Frameworks the toolkit records as the source of a converted model.
+const SOURCE_PLATFORMS: &[&str] = &[
+    "ONNX",
+    "PYTORCH",
+    "TFLITE",
+    "TENSORFLOW",
+    "CAFFE",
+    "DARKNET",
+    "KERAS",
+];
+
+/// Metadata of a single model input or output, as recorded by the toolkit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelTensor {
+    pub name: String,
+    pub io: Io,
+    pub index: u32,
+    /// Shape of the original (pre-conversion) tensor.
+    pub shape: Vec<u32>,
+    /// Layout of the original tensor, e.g. `nchw`.
+    pub layout: Option<String>,
+    /// Data type of the original tensor, e.g. `float32`.
+    pub dtype: Option<String>,
+    /// Data type the runtime expects, e.g. `int8`.
+    pub runtime_dtype: Option<String>,
+    /// Layout the runtime expects, e.g. `NHWC`.
+    pub runtime_layout: Option<String>,
+    /// Per channel mean values folded into the model.
+    pub mean: Vec<f32>,
+    /// Per channel std values folded into the model.
+    pub std: Vec<f32>,
+    pub rgb2bgr: bool,
+}
+
+/// Quantization table entry for a tensor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantEntry {
+    pub name: String,
+    pub dtype: Option<String>,
+    /// Quantization granularity, e.g. `layer` or `channel`.
+    pub qmethod: Option<String>,
+    /// Quantization type, e.g. `asym`.
+    pub qtype: Option<String>,
+    pub min: Vec<f32>,
+    pub max: Vec<f32>,
+    pub scale: Vec<f32>,
+    pub zero_point: Vec<i32>,
+}
+
+/// Parsed contents of a `.rknn` model file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelFile {
+    /// File format version from the header.
+    pub file_version: u64,
+    /// Size in bytes of the model section following the header.
+    pub model_size: u64,
+    /// Runtime generation the model was built for, e.g. `RKNPU v2`.
+    pub generator: Option<String>,
+    /// rknn-toolkit2 version, e.g. `2.3.2`.
+    pub toolkit_version: Option<String>,
+    /// Full compiler version string, e.g. `2.3.2 (@2025-04-03T08:26:16)`.
+    pub compiler_version: Option<String>,
+    /// Platforms the model was compiled for, e.g. `rk3588`.
+    pub target_platforms: Vec<String>,
+    /// Framework the model was converted from, e.g. `ONNX`.
+    pub source_platform: Option<String>,
+    pub inputs: Vec<ModelTensor>,
+    pub outputs: Vec<ModelTensor>,
+    pub quant_tab: Vec<QuantEntry>,
+    /// Raw conversion attributes, including anything not exposed above.
+    pub attrs: Option<Value>,
+    /// Graph description stored after the model section.
+    pub graph: Option<Value>,
+}
+
+impl ModelFile {
+    /// Read and parse a model file from disk.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let data = std::fs::read(path)?;
+        Self::parse(&data)
+    }
+
+    /// Parse a model file from memory.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < HEADER_SIZE || &data[..4] != MAGIC {
+            return Err(Error::ModelFileInvalid("missing RKNN magic".into()));
+        }
+
+        let file_version = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let model_size = u64::from_le_bytes(data[16..24].try_into().unwrap());
+
+        let model_end = usize::try_from(model_size)
+            .ok()
+            .and_then(|size| HEADER_SIZE.checked_add(size))
+            .filter(|end| *end <= data.len())
+            .ok_or_else(|| {
+                Error::ModelFileInvalid(format!(
+                    "model section of {} bytes exceeds file size {}",
+                    model_size,
+                    data.len()
+                ))
+            })?;
+
+        let mut model = ModelFile {
+            file_version,
+            model_size,
+            generator: None,
+            toolkit_version: None,
+            compiler_version: None,
+            target_platforms: Vec::new(),
+            source_platform: None,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            quant_tab: Vec::new(),
+            attrs: None,
+            graph: None,
+        };
+
+        let section = &data[HEADER_SIZE..model_end];
+        let root = Table::root(section, Some(MAGIC))
+            .ok_or_else(|| Error::ModelFileInvalid("model section is not readable".into()))?;
+
+        let mut runtime_io = Vec::new();
+        for s in root.strings() {
+            if s.starts_with("RKNPU") {
+                model.generator = Some(s.to_string());
+            } else if let Some((toolkit, rest)) = s.split_once("(compiler version:") {
+                model.toolkit_version = Some(toolkit.trim().to_string());
+                let compiler = rest.trim().strip_suffix(')').unwrap_or(rest.trim());
+                model.compiler_version = Some(compiler.to_string());
+            } else if is_platform_name(s) {
+                model.target_platforms.push(s.to_string());
+            } else if SOURCE_PLATFORMS.contains(&s) {
+                model.source_platform = Some(s.to_string());
+            } else if s.starts_with('{') {
+                let Ok(value) = Value::parse(s) else {
+                    continue;
+                };
+                if value.get("attrs").is_some() {
+                    model.attrs = Some(value);
+                } else if let Some(entries) = value.as_map() {
+                    runtime_io.extend(entries.iter().cloned());
+                }
+            }
+        }
+
+        if let Some(graph) = trailing_json(data, model_end) {
+            if let Some(platforms) = graph.get("target_platform").and_then(Value::as_list) {
+                let platforms: Vec<String> = platforms
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect();
+                if !platforms.is_empty() {
+                    model.target_platforms = platforms;
+                }
+            }
+            if model.source_platform.is_none() {
+                model.source_platform = graph
+                    .get("ori_network_platform")
+                    .or_else(|| graph.get("network_platform"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+            }
+            if model.toolkit_version.is_none() {
+                model.toolkit_version = graph
+                    .get("version")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+            }
+            model.graph = Some(graph);
+        }
+
+        if let Some(attrs) = &model.attrs {
+            for (name, entry) in attrs.get("attrs").and_then(Value::as_map).unwrap_or(&[]) {
+                let runtime = runtime_io.iter().find(|(n, _)| n == name).map(|(_, v)| v);
+                let tensor = ModelTensor::from_value(name, entry, runtime);
+                match tensor.io {
+                    Io::Input => model.inputs.push(tensor),
+                    Io::Output => model.outputs.push(tensor),
+                }
+            }
+            for (name, entry) in attrs
+                .get("quant_tab")
+                .and_then(Value::as_map)
+                .unwrap_or(&[])
+            {
+                model.quant_tab.push(QuantEntry::from_value(name, entry));
+            }
+        }
+        model.inputs.sort_by_key(|t| t.index);
+        model.outputs.sort_by_key(|t| t.index);
+
+        Ok(model)
+    }
+
+    /// Look up the quantization entry of a tensor by name.
+    pub fn quant_entry(&self, name: &str) -> Option<&QuantEntry> {
+        self.quant_tab.iter().find(|q| q.name == name)
+    }
+}
+
+impl ModelTensor {
+    fn from_value(name: &str, entry: &Value, runtime: Option<&Value>) -> Self {
+        let string = |v: Option<&Value>, key| {
+            v.and_then(|v| v.get(key))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        let is_output = entry
+            .get("is_output")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        ModelTensor {
+            name: name.to_string(),
+            io: if is_output { Io::Output } else { Io::Input },
+            index: entry.get("idx").and_then(Value::as_f64).unwrap_or(0.0) as u32,
+            shape: numbers(entry.get("shape")),
+            layout: string(Some(entry), "layout"),
+            dtype: string(Some(entry), "dtype"),
+            runtime_dtype: string(runtime, "dtype"),
+            runtime_layout: string(runtime, "layout"),
+            mean: numbers(entry.get("mean")),
+            std: numbers(entry.get("std")),
+            rgb2bgr: entry
+                .get("rgb2bgr")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl QuantEntry {
+    fn from_value(name: &str, entry: &Value) -> Self {
+        let string = |key| entry.get(key).and_then(Value::as_str).map(str::to_string);
+        QuantEntry {
+            name: entry
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or(name)
+                .to_string(),
+            dtype: string("dtype"),
+            qmethod: string("qmethod"),
+            qtype: string("qtype"),
+            min: numbers(entry.get("min")),
+            max: numbers(entry.get("max")),
+            scale: numbers(entry.get("scale")),
+            zero_point: numbers(entry.get("zero_point")),
+        }
+    }
+}
+
+fn numbers<T: FromF64>(value: Option<&Value>) -> Vec<T> {
+    value
+        .map(|v| v.numbers().into_iter().map(T::from_f64).collect())
+        .unwrap_or_default()
+}
+
+trait FromF64 {
+    fn from_f64(v: f64) -> Self;
+}
+
+impl FromF64 for f32 {
+    fn from_f64(v: f64) -> Self {
+        v as f32
+    }
+}
+
+impl FromF64 for u32 {
+    fn from_f64(v: f64) -> Self {
+        v as u32
+    }
+}
+
+impl FromF64 for i32 {
+    fn from_f64(v: f64) -> Self {
+        v as i32
+    }
+}
+
+/// Platform identifiers look like `rk3588`, `rv1106` or `rk2118`.
+fn is_platform_name(s: &str) -> bool {
+    (s.starts_with("rk") || s.starts_with("rv"))
+        && s.len() <= 12
+        && s[2..].starts_with(|c: char| c.is_ascii_digit())
+        && s.chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+/// The graph description is stored as a length prefixed JSON blob after the model section.
+fn trailing_json(data: &[u8], model_end: usize) -> Option<Value> {
+    let len_bytes = data.get(model_end..model_end + 8)?;
+    let len = usize::try_from(u64::from_le_bytes(len_bytes.try_into().ok()?)).ok()?;
+    let start = model_end + 8;
+    let json = data.get(start..start.checked_add(len)?)?;
+    let text = std::str::from_utf8(json).ok()?;
+    Value::parse(text.trim_end_matches('\0')).ok()
+}
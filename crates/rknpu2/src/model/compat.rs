@@ -0,0 +1,317 @@
+/// Preflight compatibility checks between a model file and a target SoC.
+use {
+    crate::{Error, model::ModelFile},
+    std::{fmt::Display, str::FromStr},
+};
+
+/// Rockchip SoCs with an RKNPU2 based NPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    Rk3562,
+    Rk3566,
+    Rk3568,
+    Rk3576,
+    Rk3588,
+    Rv1103,
+    Rv1106,
+    Rv1103B,
+    Rv1106B,
+    Rv1126B,
+    Rk2118,
+}
+
+impl Platform {
+    pub const ALL: &[Platform] = &[
+        Platform::Rk3562,
+        Platform::Rk3566,
+        Platform::Rk3568,
+        Platform::Rk3576,
+        Platform::Rk3588,
+        Platform::Rv1103,
+        Platform::Rv1106,
+        Platform::Rv1103B,
+        Platform::Rv1106B,
+        Platform::Rv1126B,
+        Platform::Rk2118,
+    ];
+
+    /// Name used by rknn-toolkit2 for `target_platform`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Platform::Rk3562 => "rk3562",
+            Platform::Rk3566 => "rk3566",
+            Platform::Rk3568 => "rk3568",
+            Platform::Rk3576 => "rk3576",
+            Platform::Rk3588 => "rk3588",
+            Platform::Rv1103 => "rv1103",
+            Platform::Rv1106 => "rv1106",
+            Platform::Rv1103B => "rv1103b",
+            Platform::Rv1106B => "rv1106b",
+            Platform::Rv1126B => "rv1126b",
+            Platform::Rk2118 => "rk2118",
+        }
+    }
+
+    /// Platforms sharing the same NPU, whose models are interchangeable.
+    const fn npu_family(self) -> u8 {
+        match self {
+            Platform::Rk3562 => 0,
+            Platform::Rk3566 | Platform::Rk3568 => 1,
+            Platform::Rk3576 => 2,
+            Platform::Rk3588 => 3,
+            Platform::Rv1103 | Platform::Rv1106 => 4,
+            Platform::Rv1103B | Platform::Rv1106B => 5,
+            Platform::Rv1126B => 6,
+            Platform::Rk2118 => 7,
+        }
+    }
+
+    /// Whether a model compiled for `other` runs on this platform.
+    pub const fn runs_models_for(self, other: Platform) -> bool {
+        self.npu_family() == other.npu_family()
+    }
+
+    /// Crate feature that has to be enabled to run on this platform.
+    pub const fn feature(self) -> &'static str {
+        match self {
+            Platform::Rk3576 | Platform::Rk3588 => "rk3576",
+            Platform::Rk3562 | Platform::Rk3566 | Platform::Rk3568 | Platform::Rv1126B => "rk35xx",
+            Platform::Rv1103 | Platform::Rv1106 | Platform::Rv1103B | Platform::Rv1106B => "rv110x",
+            Platform::Rk2118 => "rk2118",
+        }
+    }
+
+    /// SoC features enabled for this build of the crate.
+    pub fn enabled_features() -> Vec<&'static str> {
+        let mut features = Vec::new();
+        if cfg!(feature = "rk35xx") {
+            features.push("rk35xx");
+        }
+        if cfg!(feature = "rk3576") {
+            features.push("rk3576");
+        }
+        if cfg!(feature = "rv110x") {
+            features.push("rv110x");
+        }
+        if cfg!(feature = "rk2118") {
+            features.push("rk2118");
+        }
+        features
+    }
+
+    /// Detect the platform of the running machine from the device tree.
+    pub fn detect() -> Option<Platform> {
+        let compatible = std::fs::read("/proc/device-tree/compatible").ok()?;
+        Self::from_compatible(&String::from_utf8_lossy(&compatible))
+    }
+
+    /// Parse a device tree `compatible` list, e.g. `"radxa,rock-5b\0rockchip,rk3588\0"`.
+    pub fn from_compatible(compatible: &str) -> Option<Platform> {
+        compatible
+            .split('\0')
+            .filter_map(|entry| entry.strip_prefix("rockchip,"))
+            .find_map(|soc| soc.parse().ok())
+    }
+}
+
+impl FromStr for Platform {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        Platform::ALL
+            .iter()
+            .copied()
+            .find(|p| p.name() == s)
+            .ok_or(Error::ParamInvalid)
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A reason a model is not expected to load on a platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompatibilityIssue {
+    /// The model was compiled for a different SoC.
+    TargetPlatformUnmatch {
+        model: Vec<String>,
+        platform: Platform,
+    },
+    /// The model does not record which SoC it was compiled for.
+    UnknownTargetPlatform,
+    /// The crate was built without the feature needed for the platform.
+    FeatureNotEnabled {
+        platform: Platform,
+        required: &'static str,
+        enabled: Vec<&'static str>,
+    },
+    /// The model was compiled by a newer toolkit than the runtime library.
+    ToolkitNewerThanRuntime { toolkit: String, runtime: String },
+}
+
+impl CompatibilityIssue {
+    /// The error `rknn_init` would be expected to fail with.
+    pub fn as_error(&self) -> Error {
+        match self {
+            CompatibilityIssue::TargetPlatformUnmatch { .. }
+            | CompatibilityIssue::UnknownTargetPlatform
+            | CompatibilityIssue::FeatureNotEnabled { .. } => Error::TargetPlatformUnmatch,
+            CompatibilityIssue::ToolkitNewerThanRuntime { .. } => {
+                Error::IncompatibleOptimizationLevelVersion
+            }
+        }
+    }
+}
+
+impl Display for CompatibilityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompatibilityIssue::TargetPlatformUnmatch { model, platform } => write!(
+                f,
+                "model was compiled for {}, not {}",
+                model.join(", "),
+                platform
+            ),
+            CompatibilityIssue::UnknownTargetPlatform => {
+                write!(f, "model does not record a target platform")
+            }
+            CompatibilityIssue::FeatureNotEnabled {
+                platform,
+                required,
+                enabled,
+            } => write!(
+                f,
+                "{} requires the `{}` feature, enabled: [{}]",
+                platform,
+                required,
+                enabled.join(", ")
+            ),
+            CompatibilityIssue::ToolkitNewerThanRuntime { toolkit, runtime } => write!(
+                f,
+                "model was compiled by rknn-toolkit2 {} but the runtime is {}",
+                toolkit, runtime
+            ),
+        }
+    }
+}
+
+/// Result of comparing a model file against a platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatibilityReport {
+    pub platform: Platform,
+    pub model_platforms: Vec<String>,
+    pub toolkit_version: Option<String>,
+    pub runtime_version: Option<String>,
+    pub issues: Vec<CompatibilityIssue>,
+}
+
+impl CompatibilityReport {
+    pub fn is_compatible(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Also compare the toolkit version against a runtime version, as
+    /// reported by [`SdkVersion::api_version`](crate::query::SdkVersion::api_version).
+    pub fn against_runtime(mut self, api_version: &str) -> Self {
+        self.issues
+            .retain(|i| !matches!(i, CompatibilityIssue::ToolkitNewerThanRuntime { .. }));
+        if let Some(toolkit) = &self.toolkit_version
+            && let (Some(t), Some(r)) = (parse_version(toolkit), parse_version(api_version))
+            && t > r
+        {
+            self.issues
+                .push(CompatibilityIssue::ToolkitNewerThanRuntime {
+                    toolkit: toolkit.clone(),
+                    runtime: api_version.to_string(),
+                });
+        }
+        self.runtime_version = Some(api_version.to_string());
+        self
+    }
+
+    /// Convert into an error matching the first issue, if any.
+    pub fn into_result(self) -> Result<Self, Error> {
+        match self.issues.first() {
+            Some(issue) => Err(issue.as_error()),
+            None => Ok(self),
+        }
+    }
+}
+
+impl Display for CompatibilityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_compatible() {
+            return write!(f, "model is compatible with {}", self.platform);
+        }
+        write!(f, "model is not compatible with {}:", self.platform)?;
+        for issue in &self.issues {
+            write!(f, "\n  - {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Leading `major.minor.patch` numbers of a version string such as `2.3.2 (abc@date)`.
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let numeric: String = version
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let mut parts = numeric.split('.').map(|p| p.parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+impl ModelFile {
+    /// Check whether this model is expected to load on `platform` with the
+    /// SoC features this crate was built with.
+    pub fn check_compatibility(&self, platform: Platform) -> CompatibilityReport {
+        let mut issues = Vec::new();
+
+        let model_platforms: Vec<Platform> = self
+            .target_platforms
+            .iter()
+            .filter_map(|p| p.parse().ok())
+            .collect();
+        if self.target_platforms.is_empty() {
+            issues.push(CompatibilityIssue::UnknownTargetPlatform);
+        } else if !model_platforms.iter().any(|p| platform.runs_models_for(*p)) {
+            issues.push(CompatibilityIssue::TargetPlatformUnmatch {
+                model: self.target_platforms.clone(),
+                platform,
+            });
+        }
+
+        let enabled = Platform::enabled_features();
+        if !enabled.is_empty() && !enabled.contains(&platform.feature()) {
+            issues.push(CompatibilityIssue::FeatureNotEnabled {
+                platform,
+                required: platform.feature(),
+                enabled,
+            });
+        }
+
+        CompatibilityReport {
+            platform,
+            model_platforms: self.target_platforms.clone(),
+            toolkit_version: self.toolkit_version.clone(),
+            runtime_version: None,
+            issues,
+        }
+    }
+}
+
+/// Parse `model_data` and check it against `platform`.
+pub fn check_compatibility(
+    model_data: &[u8],
+    platform: Platform,
+) -> Result<CompatibilityReport, Error> {
+    Ok(ModelFile::parse(model_data)?.check_compatibility(platform))
+}
@@ -0,0 +1,85 @@
+/// Minimal, bounds-checked FlatBuffer table reader.
+///
+/// Offsets come from untrusted files, so every addition is checked to keep
+/// 32-bit targets from wrapping past the bounds checks.
+///
+/// Only what is needed to pull strings out of the model section; the RKNN
+/// schema itself is not public so fields are classified by content.
+
+#[derive(Clone, Copy)]
+pub(crate) struct Table<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+fn read_u16(buf: &[u8], pos: usize) -> Option<u16> {
+    buf.get(pos..pos.checked_add(2)?)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> Option<u32> {
+    buf.get(pos..pos.checked_add(4)?)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+impl<'a> Table<'a> {
+    /// Root table of a FlatBuffer, optionally checking the 4 byte file identifier.
+    pub(crate) fn root(buf: &'a [u8], identifier: Option<&[u8; 4]>) -> Option<Self> {
+        if let Some(id) = identifier
+            && buf.get(4..8)? != id
+        {
+            return None;
+        }
+        let pos = read_u32(buf, 0)? as usize;
+        let table = Table { buf, pos };
+        table.vtable()?;
+        Some(table)
+    }
+
+    fn vtable(&self) -> Option<(usize, usize)> {
+        let soffset = read_u32(self.buf, self.pos)? as i32 as i64;
+        let vt = usize::try_from(self.pos as i64 - soffset).ok()?;
+        let vt_len = read_u16(self.buf, vt)? as usize;
+        if vt_len < 4 || vt.checked_add(vt_len)? > self.buf.len() {
+            return None;
+        }
+        Some((vt, vt_len))
+    }
+
+    /// Number of fields declared in the vtable.
+    pub(crate) fn num_fields(&self) -> usize {
+        self.vtable().map(|(_, len)| (len - 4) / 2).unwrap_or(0)
+    }
+
+    fn field_pos(&self, field: usize) -> Option<usize> {
+        let (vt, vt_len) = self.vtable()?;
+        let entry = 4 + field * 2;
+        if entry + 2 > vt_len {
+            return None;
+        }
+        match read_u16(self.buf, vt.checked_add(entry)?)? {
+            0 => None,
+            offset => self.pos.checked_add(offset as usize),
+        }
+    }
+
+    /// Read a field as a string, returning `None` if it doesn't look like one.
+    pub(crate) fn string(&self, field: usize) -> Option<&'a str> {
+        let pos = self.field_pos(field)?;
+        let target = pos.checked_add(read_u32(self.buf, pos)? as usize)?;
+        let len = read_u32(self.buf, target)? as usize;
+        let start = target.checked_add(4)?;
+        let end = start.checked_add(len)?;
+        let bytes = self.buf.get(start..end)?;
+        // FlatBuffer strings are always NUL terminated.
+        if len == 0 || *self.buf.get(end)? != 0 {
+            return None;
+        }
+        std::str::from_utf8(bytes).ok()
+    }
+
+    /// All fields of this table that decode as strings.
+    pub(crate) fn strings(&self) -> impl Iterator<Item = &'a str> + '_ {
+        (0..self.num_fields()).filter_map(|i| self.string(i))
+    }
+}
@@ -0,0 +1,305 @@
+/// Loosely typed values embedded in model files.
+///
+/// The toolkit writes some metadata as JSON and some as Python `repr()`
+/// dictionaries (single quotes, `True`/`False`/`None`). Both are accepted.
+use crate::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    List(Vec<Value>),
+    /// Key/value pairs in the order they appear in the source.
+    Map(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Parse a JSON or Python literal string.
+    pub fn parse(text: &str) -> Result<Value, Error> {
+        let mut parser = Parser {
+            bytes: text.as_bytes(),
+            pos: 0,
+            depth: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_ws();
+        if parser.pos != parser.bytes.len() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    /// Look up a key in a map value.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Collect a list of numbers, skipping anything that isn't a number.
+    pub(crate) fn numbers(&self) -> Vec<f64> {
+        self.as_list()
+            .map(|items| items.iter().filter_map(Value::as_f64).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Deepest nesting of maps and lists accepted, so that a crafted model file
+/// fails to parse instead of overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    /// Maps and lists currently open.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, what: &str) -> Error {
+        Error::ModelFileInvalid(format!("{} at byte {}", what, self.pos))
+    }
+
+    fn skip_ws(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| b.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), Error> {
+        self.skip_ws();
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", byte as char)))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        self.skip_ws();
+        match self.peek() {
+            Some(b'{' | b'[' | b'(') if self.depth == MAX_DEPTH => {
+                Err(self.error("nesting too deep"))
+            }
+            Some(b'{') => self.map(),
+            Some(b'[') => self.list(b'[', b']'),
+            Some(b'(') => self.list(b'(', b')'),
+            Some(q @ (b'"' | b'\'')) => self.string(q).map(Value::String),
+            Some(b'-' | b'+' | b'.' | b'0'..=b'9') => self.number(),
+            Some(_) => self.word(),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn map(&mut self) -> Result<Value, Error> {
+        self.expect(b'{')?;
+        self.depth += 1;
+        let mut entries = Vec::new();
+        loop {
+            self.skip_ws();
+            if self.peek() == Some(b'}') {
+                self.pos += 1;
+                self.depth -= 1;
+                return Ok(Value::Map(entries));
+            }
+            let key = match self.value()? {
+                Value::String(s) => s,
+                Value::Number(n) => n.to_string(),
+                _ => return Err(self.error("unsupported map key")),
+            };
+            self.expect(b':')?;
+            let value = self.value()?;
+            entries.push((key, value));
+            self.skip_ws();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {}
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn list(&mut self, open: u8, close: u8) -> Result<Value, Error> {
+        self.expect(open)?;
+        self.depth += 1;
+        let mut items = Vec::new();
+        loop {
+            self.skip_ws();
+            if self.peek() == Some(close) {
+                self.pos += 1;
+                self.depth -= 1;
+                return Ok(Value::List(items));
+            }
+            items.push(self.value()?);
+            self.skip_ws();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(c) if c == close => {}
+                _ => return Err(self.error("expected ',' or end of list")),
+            }
+        }
+    }
+
+    fn string(&mut self, quote: u8) -> Result<String, Error> {
+        self.pos += 1;
+        let mut out = Vec::new();
+        loop {
+            let Some(b) = self.peek() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += 1;
+            match b {
+                b if b == quote => break,
+                b'\\' => {
+                    let Some(esc) = self.peek() else {
+                        return Err(self.error("unterminated escape"));
+                    };
+                    self.pos += 1;
+                    match esc {
+                        b'n' => out.push(b'\n'),
+                        b't' => out.push(b'\t'),
+                        b'r' => out.push(b'\r'),
+                        b'b' => out.push(0x08),
+                        b'f' => out.push(0x0c),
+                        b'0' => out.push(0),
+                        b'u' => {
+                            let hex = self
+                                .bytes
+                                .get(self.pos..self.pos + 4)
+                                .and_then(|h| std::str::from_utf8(h).ok())
+                                .and_then(|h| u32::from_str_radix(h, 16).ok())
+                                .ok_or_else(|| self.error("invalid unicode escape"))?;
+                            self.pos += 4;
+                            let c = char::from_u32(hex).unwrap_or(char::REPLACEMENT_CHARACTER);
+                            out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                        }
+                        other => out.push(other),
+                    }
+                }
+                other => out.push(other),
+            }
+        }
+        String::from_utf8(out).map_err(|_| self.error("invalid utf-8 in string"))
+    }
+
+    fn number(&mut self) -> Result<Value, Error> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| matches!(b, b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9'))
+        {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .map(Value::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+
+    fn word(&mut self) -> Result<Value, Error> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| b.is_ascii_alphanumeric() || b == b'_')
+        {
+            self.pos += 1;
+        }
+        match &self.bytes[start..self.pos] {
+            b"true" | b"True" => Ok(Value::Bool(true)),
+            b"false" | b"False" => Ok(Value::Bool(false)),
+            b"null" | b"None" => Ok(Value::Null),
+            b"inf" | b"Infinity" => Ok(Value::Number(f64::INFINITY)),
+            b"nan" | b"NaN" => Ok(Value::Number(f64::NAN)),
+            _ => {
+                self.pos = start;
+                Err(self.error("unexpected token"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_json() {
+        let v = Value::parse(r#"{"a": [1, -2.5e1, true, null], "b": "x\"y"}"#).unwrap();
+        assert_eq!(v.get("a").unwrap().numbers(), vec![1.0, -25.0]);
+        assert_eq!(v.get("b").unwrap().as_str(), Some("x\"y"));
+    }
+
+    #[test]
+    fn parses_python_literals() {
+        let v = Value::parse("{'shape': (1, 3), 'ok': False, 'none': None, 'd': {}}").unwrap();
+        assert_eq!(v.get("shape").unwrap().numbers(), vec![1.0, 3.0]);
+        assert_eq!(v.get("ok").unwrap().as_bool(), Some(false));
+        assert_eq!(v.get("none"), Some(&Value::Null));
+        assert_eq!(v.get("d").unwrap().as_map().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(Value::parse("{'a': }").is_err());
+        assert!(Value::parse("[1, 2] x").is_err());
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(Value::parse(&nested(MAX_DEPTH)).is_ok());
+        assert!(matches!(
+            Value::parse(&nested(MAX_DEPTH + 1)),
+            Err(Error::ModelFileInvalid(_))
+        ));
+        assert!(Value::parse(&"[{'a': (".repeat(100_000)).is_err());
+    }
+}
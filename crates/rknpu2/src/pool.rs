@@ -0,0 +1,218 @@
+/// Pool of contexts sharing one model, each pinned to its own NPU core.
+use {
+    crate::{Error, RKNN, api::RKNNAPI, io::zero_copy::ZeroCopySession, rknn::NpuCores},
+    std::{
+        ops::Deref,
+        sync::{
+            Mutex, MutexGuard, TryLockError,
+            atomic::{AtomicUsize, Ordering},
+        },
+    },
+};
+
+/// Core masks for one context per core on RK3588.
+pub const RK3588_CORES: &[NpuCores] = &[NpuCores::CORE0, NpuCores::CORE1, NpuCores::CORE2];
+
+/// Core masks for one context per core on RK3576.
+pub const RK3576_CORES: &[NpuCores] = &[NpuCores::CORE0, NpuCores::CORE1];
+
+/// How [`RknnPool::acquire`] picks a context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dispatch {
+    /// Cycle through the contexts in order, skipping those in use.
+    #[default]
+    RoundRobin,
+    /// Pick the context with the fewest holders and waiters.
+    LeastBusy,
+}
+
+struct Slot<A: RKNNAPI> {
+    rknn: Mutex<RKNN<A>>,
+    cores: NpuCores,
+    busy: AtomicUsize,
+}
+
+/// A set of contexts created from one model with `rknn_dup_context`, each
+/// pinned to a core mask.
+pub struct RknnPool<A: RKNNAPI> {
+    slots: Vec<Slot<A>>,
+    dispatch: Dispatch,
+    next: AtomicUsize,
+}
+
+impl<A: RKNNAPI + Clone> RknnPool<A> {
+    /// Build one context per entry in `cores`.
+    ///
+    /// `rknn` is pinned to the first mask and duplicated for the others, so
+    /// all contexts share its weights.
+    pub fn new(rknn: RKNN<A>, cores: &[NpuCores], dispatch: Dispatch) -> Result<Self, Error> {
+        let (first, rest) = cores.split_first().ok_or(Error::ParamInvalid)?;
+
+        let mut contexts = Vec::with_capacity(cores.len());
+        for _ in rest {
+            contexts.push(rknn.try_duplicate()?);
+        }
+        contexts.insert(0, rknn);
+
+        let slots = contexts
+            .into_iter()
+            .zip(std::iter::once(first).chain(rest))
+            .map(|(rknn, &cores)| {
+                rknn.set_core_mask(cores)?;
+                Ok(Slot {
+                    rknn: Mutex::new(rknn),
+                    cores,
+                    busy: AtomicUsize::new(0),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(RknnPool {
+            slots,
+            dispatch,
+            next: AtomicUsize::new(0),
+        })
+    }
+}
+
+impl<A: RKNNAPI> RknnPool<A> {
+    /// Number of contexts in the pool.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn dispatch(&self) -> Dispatch {
+        self.dispatch
+    }
+
+    fn pick(&self) -> usize {
+        match self.dispatch {
+            Dispatch::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed) % self.slots.len(),
+            Dispatch::LeastBusy => {
+                // Start the scan at a rotating offset so ties are spread out.
+                let start = self.next.fetch_add(1, Ordering::Relaxed);
+                (0..self.slots.len())
+                    .map(|i| (start + i) % self.slots.len())
+                    .min_by_key(|&i| self.slots[i].busy.load(Ordering::Relaxed))
+                    .unwrap()
+            }
+        }
+    }
+
+    /// Pick a context according to the dispatch policy.
+    ///
+    /// If the picked context is in use, the next free one after it is taken
+    /// instead; this only waits when every context is in use, and then for
+    /// the picked one.
+    pub fn acquire(&self) -> PooledRknn<'_, A> {
+        let start = self.pick();
+        for offset in 0..self.slots.len() {
+            let index = (start + offset) % self.slots.len();
+            let slot = &self.slots[index];
+            let guard = match slot.rknn.try_lock() {
+                Ok(guard) => guard,
+                Err(TryLockError::Poisoned(e)) => e.into_inner(),
+                Err(TryLockError::WouldBlock) => continue,
+            };
+            slot.busy.fetch_add(1, Ordering::Relaxed);
+            return PooledRknn { guard, slot, index };
+        }
+
+        let slot = &self.slots[start];
+        slot.busy.fetch_add(1, Ordering::Relaxed);
+        let guard = slot.rknn.lock().unwrap_or_else(|e| e.into_inner());
+        PooledRknn {
+            guard,
+            slot,
+            index: start,
+        }
+    }
+
+    /// Run `f` on a context picked by [`acquire`](Self::acquire).
+    pub fn run<R>(&self, f: impl FnOnce(&RKNN<A>) -> R) -> R {
+        f(&self.acquire())
+    }
+
+    /// Run `f` for every job, spreading the jobs over all contexts in parallel.
+    ///
+    /// Each context takes the next job as soon as it is done with the
+    /// previous one. Results are returned in submission order.
+    pub fn run_batch<T, R, F>(&self, jobs: impl IntoIterator<Item = T>, f: F) -> Vec<R>
+    where
+        A: Send,
+        T: Send,
+        R: Send,
+        F: Fn(&RKNN<A>, T) -> R + Sync,
+    {
+        let queue = Mutex::new(jobs.into_iter().enumerate().collect::<Vec<_>>().into_iter());
+        let results = Mutex::new(Vec::new());
+
+        std::thread::scope(|scope| {
+            for slot in &self.slots {
+                let (queue, results, f) = (&queue, &results, &f);
+                scope.spawn(move || {
+                    let rknn = slot.rknn.lock().unwrap_or_else(|e| e.into_inner());
+                    loop {
+                        let Some((i, job)) = queue.lock().unwrap().next() else {
+                            break;
+                        };
+                        slot.busy.fetch_add(1, Ordering::Relaxed);
+                        let result = f(&rknn, job);
+                        slot.busy.fetch_sub(1, Ordering::Relaxed);
+                        results.lock().unwrap().push((i, result));
+                    }
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap();
+        results.sort_unstable_by_key(|(i, _)| *i);
+        results.into_iter().map(|(_, r)| r).collect()
+    }
+}
+
+/// A context borrowed from an [`RknnPool`]; returned to the pool on drop.
+///
+/// Derefs to the context, but not mutably, so it cannot be swapped out of
+/// the pool. Calling [`RKNN::set_core_mask`] on it undoes the pinning to
+/// [`cores`](Self::cores).
+pub struct PooledRknn<'p, A: RKNNAPI> {
+    guard: MutexGuard<'p, RKNN<A>>,
+    slot: &'p Slot<A>,
+    index: usize,
+}
+
+impl<A: RKNNAPI> PooledRknn<'_, A> {
+    /// Position of this context in the pool.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Core mask this context is pinned to.
+    pub fn cores(&self) -> NpuCores {
+        self.slot.cores
+    }
+
+    /// Bind zero-copy inputs and outputs to this context.
+    pub fn zero_copy_session(&mut self) -> Result<ZeroCopySession<'_, A>, Error> {
+        ZeroCopySession::new(&mut self.guard)
+    }
+}
+
+impl<A: RKNNAPI> Deref for PooledRknn<'_, A> {
+    type Target = RKNN<A>;
+
+    fn deref(&self) -> &RKNN<A> {
+        &self.guard
+    }
+}
+
+impl<A: RKNNAPI> Drop for PooledRknn<'_, A> {
+    fn drop(&mut self) {
+        self.slot.busy.fetch_sub(1, Ordering::Relaxed);
+    }
+}
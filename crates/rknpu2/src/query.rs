@@ -9,8 +9,16 @@ pub trait Query: From<Self::Output> + Sized {
     type Output;
 }
 
+pub mod current_input_attr;
+pub mod current_native_input_attr;
+pub mod current_native_output_attr;
+pub mod current_output_attr;
+pub mod custom_string;
 pub mod in_out_num;
 pub mod input_attr;
+pub mod input_dynamic_range;
+pub mod mem_size;
+pub mod model_info;
 pub mod native_input_attr;
 pub mod native_nc1hwc2_input_attr;
 pub mod native_nc1hwc2_output_attr;
@@ -19,6 +27,7 @@ pub mod native_nhwc_output_attr;
 pub mod native_output_attr;
 pub mod output_attr;
 pub mod sdk_version;
+pub mod tensor_attr;
 
 #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
 #[cfg_attr(
@@ -37,11 +46,22 @@ pub mod perf_detail;
 use crate::tensor::{DataTypeKind, QuantTypeKind, TensorFormatKind};
 
 pub use {
-    in_out_num::InputOutputNum, native_input_attr::NativeInputAttr,
+    current_input_attr::CurrentInputAttr,
+    current_native_input_attr::CurrentNativeInputAttr,
+    current_native_output_attr::CurrentNativeOutputAttr,
+    current_output_attr::CurrentOutputAttr,
+    custom_string::CustomString,
+    in_out_num::InputOutputNum,
+    mem_size::MemSize,
+    model_info::ModelInfo,
+    native_input_attr::NativeInputAttr,
     native_nc1hwc2_input_attr::NativeNC1HWC2InputAttr,
     native_nc1hwc2_output_attr::NativeNC1HWC2OutputAttr,
-    native_nhwc_input_attr::NativeNHWCInputAttr, native_nhwc_output_attr::NativeNHWCOutputAttr,
-    native_output_attr::NativeOutputAttr, sdk_version::SdkVersion,
+    native_nhwc_input_attr::NativeNHWCInputAttr,
+    native_nhwc_output_attr::NativeNHWCOutputAttr,
+    native_output_attr::NativeOutputAttr,
+    sdk_version::SdkVersion,
+    tensor_attr::{AttrKind, TensorAttr},
 };
 
 #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
@@ -68,9 +88,26 @@ pub trait QueryWithInput: From<Self::Output> + Sized {
     fn prepare(input: Self::Input, output: &mut Self::Output);
 }
 
-pub use {input_attr::InputAttr, output_attr::OutputAttr};
+/// Like [`QueryWithInput`], for result structs too large for the stack.
+///
+/// # Safety
+///
+/// `Output` is allocated zeroed and handed to the runtime as is, so an
+/// all-zero bit pattern must be a valid `Output`.
+pub unsafe trait HeapQueryWithInput: for<'a> From<&'a Self::Output> + Sized {
+    const QUERY_TYPE: Type;
+
+    type Output;
+
+    type Input;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    fn prepare(input: Self::Input, output: &mut Self::Output);
+}
+
+pub use {input_attr::InputAttr, input_dynamic_range::InputDynamicRange, output_attr::OutputAttr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Io {
     Input,
     Output,
@@ -102,7 +139,7 @@ pub trait TensorAttrView {
     /// Tensor index
     fn index(&self) -> u32;
     /// Tensor name
-    fn name(&self) -> String;
+    fn name(&self) -> &str;
     /// Tensor data type
     fn dtype(&self) -> DataTypeKind;
 
@@ -136,4 +173,7 @@ pub trait TensorAttrView {
     fn size(&self) -> u32;
     /// Size in bytes
     fn size_with_stride(&self) -> u32;
+
+    /// Whether the input is passed to the NPU without conversion
+    fn pass_through(&self) -> bool;
 }
@@ -0,0 +1,10 @@
+/// Input attributes for the currently set input shape.
+use crate::query::tensor_attr::tensor_attr_query;
+
+tensor_attr_query!(
+    /// Query a specific input's attributes after the input shape was changed.
+    CurrentInputAttr,
+    RKNN_QUERY_CURRENT_INPUT_ATTR,
+    Input,
+    Current
+);
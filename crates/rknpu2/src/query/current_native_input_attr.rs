@@ -0,0 +1,10 @@
+/// Native input attributes for the currently set input shape.
+use crate::query::tensor_attr::tensor_attr_query;
+
+tensor_attr_query!(
+    /// Query a specific input's native attributes after the input shape was changed.
+    CurrentNativeInputAttr,
+    RKNN_QUERY_CURRENT_NATIVE_INPUT_ATTR,
+    Input,
+    CurrentNative
+);
@@ -0,0 +1,10 @@
+/// Native output attributes for the currently set input shape.
+use crate::query::tensor_attr::tensor_attr_query;
+
+tensor_attr_query!(
+    /// Query a specific output's native attributes after the input shape was changed.
+    CurrentNativeOutputAttr,
+    RKNN_QUERY_CURRENT_NATIVE_OUTPUT_ATTR,
+    Output,
+    CurrentNative
+);
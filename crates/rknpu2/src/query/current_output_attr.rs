@@ -0,0 +1,10 @@
+/// Output attributes for the currently set input shape.
+use crate::query::tensor_attr::tensor_attr_query;
+
+tensor_attr_query!(
+    /// Query a specific output's attributes after the input shape was changed.
+    CurrentOutputAttr,
+    RKNN_QUERY_CURRENT_OUTPUT_ATTR,
+    Output,
+    Current
+);
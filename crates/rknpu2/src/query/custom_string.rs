@@ -0,0 +1,136 @@
+/// User defined string embedded in the model by the toolkit.
+use rknpu2_sys::{
+    _rknn_query_cmd::{RKNN_QUERY_CUSTOM_STRING, Type},
+    rknn_custom_string,
+};
+
+use crate::{Error, model::Value, query::Query};
+
+/// Query the custom string set with `custom_string` when converting the model.
+pub struct CustomString {
+    pub(crate) inner: rknn_custom_string,
+}
+
+impl CustomString {
+    /// The raw string, empty if none was set.
+    pub fn string(&self) -> String {
+        // The buffer is not guaranteed to be NUL terminated when it is full.
+        let bytes: Vec<u8> = self
+            .inner
+            .string
+            .iter()
+            .take_while(|c| **c != 0)
+            .map(|c| *c as u8)
+            .collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.string[0] == 0
+    }
+
+    /// Parse the string as JSON (or a Python dict literal).
+    pub fn parse_json(&self) -> Result<Value, Error> {
+        parse_value(&self.string())
+    }
+
+    /// Parse `key=value` entries separated by newlines or `;`.
+    pub fn parse_key_values(&self) -> Result<Value, Error> {
+        parse_key_values(&self.string())
+    }
+
+    /// Parse the string as JSON if it is an object, otherwise as `key=value`
+    /// entries. Always returns a [`Value::Map`]; an empty string gives an empty map.
+    pub fn metadata(&self) -> Result<Value, Error> {
+        parse_metadata(&self.string())
+    }
+}
+
+/// [`Value::parse`], reporting syntax errors as [`Error::CustomStringInvalid`].
+fn parse_value(text: &str) -> Result<Value, Error> {
+    Value::parse(text).map_err(|err| match err {
+        Error::ModelFileInvalid(reason) => Error::CustomStringInvalid(reason),
+        err => err,
+    })
+}
+
+fn parse_metadata(text: &str) -> Result<Value, Error> {
+    if text.trim_start().starts_with('{') {
+        match parse_value(text)? {
+            map @ Value::Map(_) => Ok(map),
+            _ => Err(Error::CustomStringInvalid("not an object".to_string())),
+        }
+    } else {
+        parse_key_values(text)
+    }
+}
+
+fn parse_key_values(text: &str) -> Result<Value, Error> {
+    let mut entries = Vec::new();
+    for entry in text.split(['\n', ';']).map(str::trim) {
+        if entry.is_empty() {
+            continue;
+        }
+        let (key, value) = entry
+            .split_once('=')
+            .ok_or_else(|| Error::CustomStringInvalid(format!("entry without '=': {}", entry)))?;
+        let value = value.trim();
+        // Bare words such as labels stay strings; numbers, lists etc. are typed.
+        let value = Value::parse(value).unwrap_or_else(|_| Value::String(value.to_string()));
+        entries.push((key.trim().to_string(), value));
+    }
+    Ok(Value::Map(entries))
+}
+
+impl Query for CustomString {
+    const QUERY_TYPE: Type = RKNN_QUERY_CUSTOM_STRING;
+
+    type Output = rknn_custom_string;
+}
+
+impl From<rknn_custom_string> for CustomString {
+    fn from(value: rknn_custom_string) -> Self {
+        CustomString { inner: value }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_key_values() {
+        let v = parse_metadata(
+            "classes=3\nlabels=[\"cat\", \"dog\", \"bird\"]; mean = 127.5\nversion=v1.2",
+        )
+        .unwrap();
+        assert_eq!(v.get("classes").unwrap().as_f64(), Some(3.0));
+        assert_eq!(v.get("labels").unwrap().as_list().unwrap().len(), 3);
+        assert_eq!(v.get("mean").unwrap().as_f64(), Some(127.5));
+        assert_eq!(v.get("version").unwrap().as_str(), Some("v1.2"));
+    }
+
+    #[test]
+    fn parses_json_object() {
+        let v = parse_metadata(r#"{"classes": 2, "labels": ["a", "b"]}"#).unwrap();
+        assert_eq!(v.get("classes").unwrap().as_f64(), Some(2.0));
+        assert_eq!(parse_metadata("").unwrap(), Value::Map(Vec::new()));
+        assert!(matches!(
+            parse_metadata("just text"),
+            Err(Error::CustomStringInvalid(_))
+        ));
+        assert!(matches!(
+            parse_metadata(r#"{"classes": }"#),
+            Err(Error::CustomStringInvalid(_))
+        ));
+    }
+
+    #[test]
+    fn reads_unterminated_string() {
+        let custom = CustomString::from(rknn_custom_string {
+            string: [b'a' as _; 1024],
+        });
+        assert_eq!(custom.string().len(), 1024);
+        assert!(!custom.is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+/// Input shapes supported by a dynamic-shape model.
+use {
+    crate::{
+        query::{HeapQueryWithInput, tensor_attr::name_from_sys},
+        tensor::TensorFormatKind,
+    },
+    rknpu2_sys::{
+        _rknn_query_cmd::{RKNN_QUERY_INPUT_DYNAMIC_RANGE, Type},
+        rknn_input_range,
+    },
+};
+
+/// Query the shapes an input of a dynamic-shape model can be set to.
+///
+/// `rknn_input_range` is about 32KB, so this is queried with
+/// [`RKNN::query_heap_with_input`](crate::RKNN::query_heap_with_input).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputDynamicRange {
+    index: u32,
+    fmt: TensorFormatKind,
+    name: String,
+    shapes: Vec<Vec<u32>>,
+}
+
+impl InputDynamicRange {
+    /// Input index
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Tensor format the shapes are expressed in
+    pub fn format(&self) -> TensorFormatKind {
+        self.fmt
+    }
+
+    /// Tensor name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Every supported shape; empty for inputs with a static shape.
+    pub fn shapes(&self) -> &[Vec<u32>] {
+        &self.shapes
+    }
+}
+
+// SAFETY: `rknn_input_range` is plain C data; all zeroes is valid.
+unsafe impl HeapQueryWithInput for InputDynamicRange {
+    const QUERY_TYPE: Type = RKNN_QUERY_INPUT_DYNAMIC_RANGE;
+
+    type Output = rknn_input_range;
+    type Input = u32;
+
+    fn prepare(input: Self::Input, output: &mut Self::Output) {
+        output.index = input;
+    }
+}
+
+impl From<&rknn_input_range> for InputDynamicRange {
+    fn from(range: &rknn_input_range) -> Self {
+        let n_dims = (range.n_dims as usize).min(range.dyn_range[0].len());
+        let shapes = range
+            .dyn_range
+            .iter()
+            .take(range.shape_number as usize)
+            .map(|dims| dims[..n_dims].to_vec())
+            .collect();
+
+        InputDynamicRange {
+            index: range.index,
+            fmt: range.fmt.into(),
+            name: name_from_sys(&range.name),
+            shapes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_reported_shapes_and_dims() {
+        let mut range: Box<rknn_input_range> = unsafe { Box::new_zeroed().assume_init() };
+        range.index = 1;
+        range.shape_number = 2;
+        range.n_dims = 4;
+        range.dyn_range[0][..4].copy_from_slice(&[1, 224, 224, 3]);
+        range.dyn_range[1][..4].copy_from_slice(&[1, 320, 320, 3]);
+        range.name[0] = b'x' as _;
+
+        let range = InputDynamicRange::from(&*range);
+        assert_eq!(range.index(), 1);
+        assert_eq!(range.name(), "x");
+        assert_eq!(
+            range.shapes(),
+            &[vec![1, 224, 224, 3], vec![1, 320, 320, 3]]
+        );
+    }
+
+    #[test]
+    fn reads_unterminated_name_within_bounds() {
+        let mut range: Box<rknn_input_range> = unsafe { Box::new_zeroed().assume_init() };
+        range.name.fill(b'a' as _);
+
+        let range = InputDynamicRange::from(&*range);
+        assert_eq!(range.name().len(), rknpu2_sys::RKNN_MAX_NAME_LEN as usize);
+        assert!(range.name().bytes().all(|b| b == b'a'));
+    }
+}
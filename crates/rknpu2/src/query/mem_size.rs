@@ -0,0 +1,81 @@
+/// Memory used by the model.
+use rknpu2_sys::{
+    _rknn_query_cmd::{RKNN_QUERY_MEM_SIZE, Type},
+    rknn_mem_size,
+};
+
+use crate::query::Query;
+
+/// Query how much weight, internal, DMA and SRAM memory the context uses.
+///
+/// All sizes are in bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct MemSize {
+    pub(crate) inner: rknn_mem_size,
+}
+
+impl MemSize {
+    /// Memory holding the model weights.
+    pub fn total_weight_size(&self) -> u64 {
+        self.inner.total_weight_size as u64
+    }
+
+    /// Memory holding intermediate tensors.
+    pub fn total_internal_size(&self) -> u64 {
+        self.inner.total_internal_size as u64
+    }
+
+    /// All DMA memory allocated by the context.
+    pub fn total_dma_allocated_size(&self) -> u64 {
+        self.inner.total_dma_allocated_size
+    }
+
+    /// SRAM reserved for the context, 0 if SRAM is not enabled.
+    pub fn total_sram_size(&self) -> u64 {
+        self.inner.total_sram_size as u64
+    }
+
+    /// SRAM still free for other contexts.
+    pub fn free_sram_size(&self) -> u64 {
+        self.inner.free_sram_size as u64
+    }
+}
+
+impl Query for MemSize {
+    const QUERY_TYPE: Type = RKNN_QUERY_MEM_SIZE;
+
+    type Output = rknn_mem_size;
+}
+
+impl From<rknn_mem_size> for MemSize {
+    fn from(value: rknn_mem_size) -> Self {
+        MemSize { inner: value }
+    }
+}
+
+impl PartialEq for MemSize {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_weight_size() == other.total_weight_size()
+            && self.total_internal_size() == other.total_internal_size()
+            && self.total_dma_allocated_size() == other.total_dma_allocated_size()
+            && self.total_sram_size() == other.total_sram_size()
+            && self.free_sram_size() == other.free_sram_size()
+    }
+}
+
+impl Eq for MemSize {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MemSize {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("MemSize", 5)?;
+        s.serialize_field("total_weight_size", &self.total_weight_size())?;
+        s.serialize_field("total_internal_size", &self.total_internal_size())?;
+        s.serialize_field("total_dma_allocated_size", &self.total_dma_allocated_size())?;
+        s.serialize_field("total_sram_size", &self.total_sram_size())?;
+        s.serialize_field("free_sram_size", &self.free_sram_size())?;
+        s.end()
+    }
+}
@@ -0,0 +1,150 @@
+/// Snapshot of everything the runtime reports about a loaded model.
+use {
+    crate::query::{MemSize, TensorAttr},
+    std::fmt::Display,
+};
+
+/// Owned summary returned by [`RKNN::model_info`](crate::RKNN::model_info).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ModelInfo {
+    /// Runtime library version.
+    pub api_version: String,
+    /// NPU driver version.
+    pub driver_version: String,
+    /// `RKNN_QUERY_INPUT_ATTR` for every input.
+    pub inputs: Vec<TensorAttr>,
+    /// `RKNN_QUERY_OUTPUT_ATTR` for every output.
+    pub outputs: Vec<TensorAttr>,
+    /// `RKNN_QUERY_NATIVE_INPUT_ATTR` for every input.
+    pub native_inputs: Vec<TensorAttr>,
+    /// `RKNN_QUERY_NATIVE_OUTPUT_ATTR` for every output.
+    pub native_outputs: Vec<TensorAttr>,
+    /// `RKNN_QUERY_MEM_SIZE` for the context, `None` if the query failed.
+    pub mem_size: Option<MemSize>,
+    /// Custom string embedded by the toolkit, `None` if the query failed.
+    pub custom_string: Option<String>,
+}
+
+/// Formatted like the startup output of the rknn_api C demos.
+impl Display for ModelInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "sdk version: {} driver version: {}",
+            self.api_version, self.driver_version
+        )?;
+        write!(
+            f,
+            "\nmodel input num: {}, output num: {}",
+            self.inputs.len(),
+            self.outputs.len()
+        )?;
+        if let Some(mem_size) = &self.mem_size {
+            write!(
+                f,
+                "\nweight size: {}, internal size: {}, dma size: {}",
+                mem_size.total_weight_size(),
+                mem_size.total_internal_size(),
+                mem_size.total_dma_allocated_size()
+            )?;
+        }
+        for (title, attrs) in [
+            ("input tensors", &self.inputs),
+            ("output tensors", &self.outputs),
+            ("native input tensors", &self.native_inputs),
+            ("native output tensors", &self.native_outputs),
+        ] {
+            write!(f, "\n{}:", title)?;
+            for attr in attrs {
+                write!(f, "\n  {}", attr)?;
+            }
+        }
+        if let Some(custom_string) = self.custom_string.as_deref().filter(|s| !s.is_empty()) {
+            write!(f, "\ncustom string: {}", custom_string)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::query::{AttrKind, Io},
+        rknpu2_sys::{
+            _rknn_tensor_format, _rknn_tensor_qnt_type, _rknn_tensor_type, rknn_mem_size,
+            rknn_tensor_attr,
+        },
+    };
+
+    fn attr(io: Io) -> TensorAttr {
+        let mut attr: rknn_tensor_attr = unsafe { std::mem::zeroed() };
+        attr.n_dims = 2;
+        attr.dims[..2].copy_from_slice(&[1, 1000]);
+        attr.n_elems = 1000;
+        attr.size = 1000;
+        attr.size_with_stride = 1000;
+        attr.fmt = _rknn_tensor_format::RKNN_TENSOR_UNDEFINED;
+        attr.type_ = _rknn_tensor_type::RKNN_TENSOR_INT8;
+        attr.qnt_type = _rknn_tensor_qnt_type::RKNN_TENSOR_QNT_AFFINE_ASYMMETRIC;
+        attr.zp = -128;
+        attr.scale = 0.25;
+        attr.name[..3].copy_from_slice(&[b'o' as _, b'u' as _, b't' as _]);
+        TensorAttr::from_sys(&attr, io, AttrKind::Plain)
+    }
+
+    fn mem_size() -> MemSize {
+        let mut mem_size: rknn_mem_size = unsafe { std::mem::zeroed() };
+        mem_size.total_weight_size = 4096;
+        mem_size.total_internal_size = 1024;
+        mem_size.total_dma_allocated_size = 5120;
+        MemSize::from(mem_size)
+    }
+
+    #[test]
+    fn display_matches_demo_output() {
+        let info = ModelInfo {
+            api_version: "2.3.2".to_string(),
+            driver_version: "0.9.8".to_string(),
+            inputs: vec![],
+            outputs: vec![attr(Io::Output)],
+            native_inputs: vec![],
+            native_outputs: vec![],
+            mem_size: Some(mem_size()),
+            custom_string: Some("labels=imagenet".to_string()),
+        };
+
+        assert_eq!(
+            info.to_string(),
+            "sdk version: 2.3.2 driver version: 0.9.8\n\
+             model input num: 0, output num: 1\n\
+             weight size: 4096, internal size: 1024, dma size: 5120\n\
+             input tensors:\n\
+             output tensors:\n  \
+             index=0, name=out, n_dims=2, dims=[1, 1000], n_elems=1000, size=1000, \
+             w_stride = 0, size_with_stride=1000, fmt=UNDEFINED, type=INT8, \
+             qnt_type=AFFINE, zp=-128, scale=0.250000\n\
+             native input tensors:\n\
+             native output tensors:\n\
+             custom string: labels=imagenet"
+        );
+    }
+
+    #[test]
+    fn display_skips_missing_mem_size_and_custom_string() {
+        let info = ModelInfo {
+            api_version: "2.3.2".to_string(),
+            driver_version: "0.9.8".to_string(),
+            inputs: vec![],
+            outputs: vec![],
+            native_inputs: vec![],
+            native_outputs: vec![],
+            mem_size: None,
+            custom_string: None,
+        };
+
+        assert!(!info.to_string().contains("weight size"));
+        assert!(!info.to_string().contains("custom string"));
+    }
+}
@@ -35,4 +35,368 @@ impl PerfDetail {
         };
         unsafe { std::str::from_utf8_unchecked(s) }
     }
+
+    /// Parse the layer table in [`details`](Self::details).
+    ///
+    /// Rows that cannot be parsed are skipped.
+    pub fn layers(&self) -> Vec<LayerPerf> {
+        parse_layers(self.details())
+    }
+}
+
+/// NPU cycles spent on one layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Cycles {
+    pub ddr: u64,
+    pub npu: u64,
+    pub total: u64,
+}
+
+/// One row of the runtime's layer information table.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LayerPerf {
+    pub id: u32,
+    pub op_type: String,
+    pub data_type: String,
+    /// `NPU`, `CPU` or `GPU`.
+    pub target: String,
+    /// Empty for the input operator.
+    pub input_shapes: Vec<Vec<u32>>,
+    /// Empty for the output operator.
+    pub output_shapes: Vec<Vec<u32>>,
+    pub cycles: Cycles,
+    pub time_us: u64,
+    /// MAC utilization in percent, only reported for NPU layers.
+    pub mac_usage: Option<f32>,
+    /// Share of the work done by each NPU core in percent, empty if not reported.
+    pub workload: Vec<f32>,
+    /// Memory read and written, in KiB.
+    pub rw_kb: Option<f32>,
+    pub full_name: String,
+}
+
+impl LayerPerf {
+    /// Whether the layer fell back to the CPU. The input and output operators
+    /// always run on the CPU and are not counted.
+    pub fn is_cpu_fallback(&self) -> bool {
+        self.target == "CPU" && !matches!(self.op_type.as_str(), "InputOperator" | "OutputOperator")
+    }
+}
+
+/// The `n` slowest layers, slowest first.
+pub fn slowest(layers: &[LayerPerf], n: usize) -> Vec<&LayerPerf> {
+    let mut sorted = layers.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|l| std::cmp::Reverse(l.time_us));
+    sorted.truncate(n);
+    sorted
+}
+
+/// Total time per op type in µs, slowest first.
+pub fn time_by_op_type(layers: &[LayerPerf]) -> Vec<(String, u64)> {
+    let mut totals: Vec<(String, u64)> = Vec::new();
+    for layer in layers {
+        match totals.iter_mut().find(|(op, _)| *op == layer.op_type) {
+            Some((_, time)) => *time += layer.time_us,
+            None => totals.push((layer.op_type.clone(), layer.time_us)),
+        }
+    }
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    totals
+}
+
+/// Layers that fell back to the CPU, see [`LayerPerf::is_cpu_fallback`].
+pub fn cpu_fallback(layers: &[LayerPerf]) -> Vec<&LayerPerf> {
+    layers.iter().filter(|l| l.is_cpu_fallback()).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Id,
+    OpType,
+    DataType,
+    Target,
+    InputShape,
+    OutputShape,
+    Cycles,
+    DdrCycles,
+    NpuCycles,
+    TotalCycles,
+    Time,
+    MacUsage,
+    WorkLoad,
+    Rw,
+    FullName,
+    Other,
+}
+
+// Longer labels first so that e.g. "DDR Cycles" is not split into two columns.
+const HEADERS: &[(&str, Column)] = &[
+    ("Cycles(DDR/NPU/Total)", Column::Cycles),
+    ("WorkLoad(0/1/2)", Column::WorkLoad),
+    ("Total Cycles", Column::TotalCycles),
+    ("OutputShape", Column::OutputShape),
+    ("MacUsage(%)", Column::MacUsage),
+    ("InputShape", Column::InputShape),
+    ("DDR Cycles", Column::DdrCycles),
+    ("NPU Cycles", Column::NpuCycles),
+    ("Task Number", Column::Other),
+    ("Lut Number", Column::Other),
+    ("DataType", Column::DataType),
+    ("Time(us)", Column::Time),
+    ("FullName", Column::FullName),
+    ("OpType", Column::OpType),
+    ("Target", Column::Target),
+    ("RW(KB)", Column::Rw),
+    ("ID", Column::Id),
+];
+
+/// Whitespace separated words with their byte offsets.
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                tokens.push((s, &line[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((s, &line[s..]));
+    }
+    tokens
+}
+
+fn parse_header(line: &str) -> Vec<(usize, Column)> {
+    let mut columns = Vec::new();
+    let mut pos = 0;
+    while pos < line.len() {
+        let rest = &line[pos..];
+        let trimmed = rest.trim_start();
+        if trimmed.is_empty() {
+            break;
+        }
+        pos += rest.len() - trimmed.len();
+
+        let (len, column) = HEADERS
+            .iter()
+            .find(|(label, _)| trimmed.starts_with(label))
+            .map(|(label, column)| (label.len(), *column))
+            .unwrap_or_else(|| {
+                let len = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+                (len, Column::Other)
+            });
+        columns.push((pos, column));
+        pos += len;
+    }
+    columns
+}
+
+/// `(1,3,224,224),(32)` into dims per tensor; `\` means no tensor.
+fn parse_shapes(text: &str) -> Vec<Vec<u32>> {
+    if text == "\\" {
+        return Vec::new();
+    }
+    text.split("),(")
+        .map(|shape| {
+            shape
+                .trim_matches(|c| c == '(' || c == ')')
+                .split(',')
+                .filter(|d| !d.is_empty())
+                .map(|d| d.parse().ok())
+                .collect::<Option<Vec<u32>>>()
+        })
+        .collect::<Option<Vec<_>>>()
+        .unwrap_or_default()
+}
+
+fn parse_row(columns: &[(usize, Column)], line: &str) -> Option<LayerPerf> {
+    let tokens = tokens(line);
+    let time_col = columns.iter().position(|(_, c)| *c == Column::Time)?;
+    let mut layer = LayerPerf::default();
+
+    // Up to the time every column holds exactly one word, but an overlong
+    // shape pushes the following columns to the right.
+    let (lead, tail) = columns.split_at(time_col + 1);
+    if tokens.len() < lead.len() {
+        return None;
+    }
+    for (&(_, column), &(_, value)) in lead.iter().zip(&tokens) {
+        match column {
+            Column::Id => layer.id = value.parse().ok()?,
+            Column::OpType => layer.op_type = value.to_string(),
+            Column::DataType => layer.data_type = value.to_string(),
+            Column::Target => layer.target = value.to_string(),
+            Column::InputShape => layer.input_shapes = parse_shapes(value),
+            Column::OutputShape => layer.output_shapes = parse_shapes(value),
+            Column::Cycles => {
+                let mut parts = value.split('/').map(|v| v.parse().ok());
+                layer.cycles = Cycles {
+                    ddr: parts.next()??,
+                    npu: parts.next()??,
+                    total: parts.next()??,
+                };
+            }
+            Column::DdrCycles => layer.cycles.ddr = value.parse().ok()?,
+            Column::NpuCycles => layer.cycles.npu = value.parse().ok()?,
+            Column::TotalCycles => layer.cycles.total = value.parse().ok()?,
+            Column::Time => layer.time_us = value.parse().ok()?,
+            _ => {}
+        }
+    }
+
+    // The remaining columns may be blank, so words are matched to columns
+    // by position, relative to where the time column ended up.
+    let (time_pos, _) = tokens[time_col];
+    let shift = time_pos as isize - columns[time_col].0 as isize;
+    for &(start, value) in &tokens[lead.len()..] {
+        let pos = start as isize - shift;
+        let Some(&(_, column)) = tail.iter().rev().find(|(start, _)| *start as isize <= pos) else {
+            continue;
+        };
+        match column {
+            Column::MacUsage => layer.mac_usage = value.parse().ok(),
+            Column::WorkLoad => {
+                layer.workload = value
+                    .split('/')
+                    .filter_map(|v| v.trim_end_matches('%').parse().ok())
+                    .collect();
+            }
+            Column::Rw => layer.rw_kb = value.parse().ok(),
+            Column::FullName => {
+                layer.full_name = line[start..].trim_end().to_string();
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    Some(layer)
+}
+
+fn parse_layers(text: &str) -> Vec<LayerPerf> {
+    let mut columns: Option<Vec<(usize, Column)>> = None;
+    let mut layers = Vec::new();
+
+    for line in text.lines() {
+        let Some(&(_, first)) = tokens(line).first() else {
+            continue;
+        };
+        if first == "ID" {
+            columns = Some(parse_header(line));
+        } else if first.bytes().all(|b| b.is_ascii_digit()) {
+            if let Some(layer) = columns.as_deref().and_then(|c| parse_row(c, line)) {
+                layers.push(layer);
+            }
+        } else if line.starts_with("Total") {
+            // End of the layer table.
+            columns = None;
+        }
+    }
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static V2: &str = include_str!("../../tests/fixtures/perf_detail_v2.txt");
+    static V1: &str = include_str!("../../tests/fixtures/perf_detail_v1.txt");
+
+    #[test]
+    fn parses_layer_table() {
+        let layers = parse_layers(V2);
+        assert_eq!(layers.len(), 8);
+
+        let input = &layers[0];
+        assert_eq!(input.op_type, "InputOperator");
+        assert!(input.input_shapes.is_empty());
+        assert_eq!(input.output_shapes, vec![vec![1, 224, 224, 3]]);
+        assert_eq!(input.mac_usage, None);
+        assert!(input.workload.is_empty());
+        assert_eq!(input.rw_kb, Some(0.0));
+        assert_eq!(input.full_name, "InputOperator:input");
+
+        let conv = &layers[1];
+        assert_eq!(conv.id, 2);
+        assert_eq!(conv.data_type, "INT8");
+        assert_eq!(conv.target, "NPU");
+        assert_eq!(
+            conv.input_shapes,
+            vec![vec![1, 224, 224, 3], vec![32, 3, 3, 3], vec![32]]
+        );
+        assert_eq!(
+            conv.cycles,
+            Cycles {
+                ddr: 56532,
+                npu: 200704,
+                total: 200704
+            }
+        );
+        assert_eq!(conv.time_us, 412);
+        assert_eq!(conv.mac_usage, Some(1.22));
+        assert_eq!(conv.workload, vec![100.0, 0.0, 0.0]);
+        assert_eq!(conv.full_name, "Conv:Conv_0");
+    }
+
+    #[test]
+    fn overlong_shape_shifts_columns() {
+        let layer = &parse_layers(V2)[6];
+        assert_eq!(layer.input_shapes.len(), 5);
+        assert_eq!(layer.output_shapes, vec![vec![1, 96, 112, 112]]);
+        assert_eq!(layer.time_us, 502);
+        assert_eq!(layer.mac_usage, Some(0.65));
+        assert_eq!(layer.workload, vec![33.3, 33.3, 33.3]);
+        assert_eq!(layer.rw_kb, Some(1765.0));
+        assert_eq!(layer.full_name, "Conv:Conv_7");
+    }
+
+    #[test]
+    fn parses_separate_cycle_columns() {
+        let layers = parse_layers(V1);
+        assert_eq!(layers.len(), 3);
+        assert_eq!(
+            layers[1].cycles,
+            Cycles {
+                ddr: 30357,
+                npu: 163072,
+                total: 163072
+            }
+        );
+        assert_eq!(layers[1].time_us, 830);
+        assert_eq!(layers[1].mac_usage, Some(0.65));
+        assert_eq!(layers[1].rw_kb, Some(151.17));
+        assert_eq!(layers[0].mac_usage, None);
+        assert_eq!(layers[0].rw_kb, Some(147.0));
+        assert_eq!(layers[2].full_name, "OutputOperator:output");
+    }
+
+    #[test]
+    fn aggregates() {
+        let layers = parse_layers(V2);
+
+        let slowest = slowest(&layers, 2);
+        assert_eq!(slowest.iter().map(|l| l.id).collect::<Vec<_>>(), vec![7, 2]);
+
+        let by_op = time_by_op_type(&layers);
+        assert_eq!(by_op[0], ("ConvRelu".to_string(), 1219));
+        assert_eq!(by_op[1], ("Conv".to_string(), 190));
+        assert_eq!(by_op.iter().map(|(_, t)| t).sum::<u64>(), 1475);
+
+        let cpu = cpu_fallback(&layers);
+        assert_eq!(
+            cpu.iter().map(|l| l.op_type.as_str()).collect::<Vec<_>>(),
+            vec!["Softmax", "Reshape"]
+        );
+    }
+
+    #[test]
+    fn empty_details() {
+        assert!(parse_layers("").is_empty());
+    }
 }
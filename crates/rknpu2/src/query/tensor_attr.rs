@@ -0,0 +1,426 @@
+/// Owned tensor attributes shared by all attribute queries.
+use {
+    crate::{
+        query::{Io, TensorAttrView},
+        tensor::{DataTypeKind, QuantTypeKind, TensorFormatKind},
+    },
+    rknpu2_sys::rknn_tensor_attr,
+    std::{
+        ffi::{CStr, c_char},
+        fmt::Display,
+        slice,
+    },
+};
+
+/// Which attribute query a [`TensorAttr`] was returned by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AttrKind {
+    /// `RKNN_QUERY_INPUT_ATTR` / `RKNN_QUERY_OUTPUT_ATTR`
+    Plain,
+    /// `RKNN_QUERY_NATIVE_INPUT_ATTR` / `RKNN_QUERY_NATIVE_OUTPUT_ATTR`
+    Native,
+    /// `RKNN_QUERY_NATIVE_NHWC_INPUT_ATTR` / `RKNN_QUERY_NATIVE_NHWC_OUTPUT_ATTR`
+    NativeNhwc,
+    /// `RKNN_QUERY_NATIVE_NC1HWC2_INPUT_ATTR` / `RKNN_QUERY_NATIVE_NC1HWC2_OUTPUT_ATTR`
+    NativeNc1hwc2,
+    /// `RKNN_QUERY_CURRENT_INPUT_ATTR` / `RKNN_QUERY_CURRENT_OUTPUT_ATTR`
+    Current,
+    /// `RKNN_QUERY_CURRENT_NATIVE_INPUT_ATTR` / `RKNN_QUERY_CURRENT_NATIVE_OUTPUT_ATTR`
+    CurrentNative,
+}
+
+/// Attributes of an input or output tensor.
+///
+/// With the `serde` feature the format, data type and quantization type
+/// serialize as the same names [`Display`] uses.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TensorAttr {
+    io: Io,
+    kind: AttrKind,
+    index: u32,
+    name: String,
+    dims: Vec<u32>,
+    n_elems: u32,
+    size: u32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+    fmt: TensorFormatKind,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+    dtype: DataTypeKind,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+    qnt_type: QuantTypeKind,
+    fl: i8,
+    zp: i32,
+    scale: f32,
+    w_stride: u32,
+    h_stride: u32,
+    size_with_stride: u32,
+    pass_through: bool,
+}
+
+impl TensorAttr {
+    pub fn from_sys(attr: &rknn_tensor_attr, io: Io, kind: AttrKind) -> Self {
+        let n_dims = (attr.n_dims as usize).min(attr.dims.len());
+
+        TensorAttr {
+            io,
+            kind,
+            index: attr.index,
+            name: name_from_sys(&attr.name),
+            dims: attr.dims[..n_dims].to_vec(),
+            n_elems: attr.n_elems,
+            size: attr.size,
+            fmt: attr.fmt.into(),
+            dtype: attr.type_.into(),
+            qnt_type: attr.qnt_type.into(),
+            fl: attr.fl,
+            zp: attr.zp,
+            scale: attr.scale,
+            w_stride: attr.w_stride,
+            h_stride: attr.h_stride,
+            size_with_stride: attr.size_with_stride,
+            pass_through: attr.pass_through != 0,
+        }
+    }
+
+    /// Which query these attributes came from.
+    pub fn kind(&self) -> AttrKind {
+        self.kind
+    }
+
+    /// Convert back into the C struct, e.g. for `rknn_set_input_shapes`.
+    ///
+    /// Names longer than the C buffer are truncated.
+    pub fn to_sys(&self) -> rknn_tensor_attr {
+        // SAFETY: rknn_tensor_attr is plain C data for which all zeroes is valid.
+        let mut attr: rknn_tensor_attr = unsafe { std::mem::zeroed() };
+        attr.index = self.index;
+        attr.n_dims = self.dims.len() as u32;
+        attr.dims[..self.dims.len()].copy_from_slice(&self.dims);
+        let name_len = attr.name.len() - 1;
+        for (dst, src) in attr.name.iter_mut().zip(self.name.bytes().take(name_len)) {
+            *dst = src as _;
+        }
+        attr.n_elems = self.n_elems;
+        attr.size = self.size;
+        attr.fmt = self.fmt.into();
+        attr.type_ = self.dtype.into();
+        attr.qnt_type = self.qnt_type.into();
+        attr.fl = self.fl;
+        attr.zp = self.zp;
+        attr.scale = self.scale;
+        attr.w_stride = self.w_stride;
+        attr.h_stride = self.h_stride;
+        attr.size_with_stride = self.size_with_stride;
+        attr.pass_through = self.pass_through as u8;
+        attr
+    }
+
+    /// Replace the dims, e.g. before passing [`to_sys`](Self::to_sys) to
+    /// `rknn_set_input_shapes`. At most 16 dims are supported.
+    pub fn with_dims(mut self, dims: &[u32], fmt: TensorFormatKind) -> Self {
+        self.dims = dims[..dims.len().min(16)].to_vec();
+        self.fmt = fmt;
+        self
+    }
+}
+
+impl PartialEq for TensorAttr {
+    fn eq(&self, other: &Self) -> bool {
+        self.io == other.io
+            && self.kind == other.kind
+            && self.index == other.index
+            && self.name == other.name
+            && self.dims == other.dims
+            && self.n_elems == other.n_elems
+            && self.size == other.size
+            && self.fmt == other.fmt
+            && self.dtype == other.dtype
+            && self.qnt_type == other.qnt_type
+            && self.fl == other.fl
+            && self.zp == other.zp
+            // Bitwise, so that `Eq` holds.
+            && self.scale.to_bits() == other.scale.to_bits()
+            && self.w_stride == other.w_stride
+            && self.h_stride == other.h_stride
+            && self.size_with_stride == other.size_with_stride
+            && self.pass_through == other.pass_through
+    }
+}
+
+impl Eq for TensorAttr {}
+
+/// Formatted like `dump_tensor_attr` in the rknn_api C demos.
+impl Display for TensorAttr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dims = self
+            .dims
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(
+            f,
+            "index={}, name={}, n_dims={}, dims=[{}], n_elems={}, size={}, w_stride = {}, \
+             size_with_stride={}, fmt={}, type={}, qnt_type={}, zp={}, scale={:.6}",
+            self.index,
+            self.name,
+            self.dims.len(),
+            dims,
+            self.n_elems,
+            self.size,
+            self.w_stride,
+            self.size_with_stride,
+            self.fmt,
+            self.dtype,
+            self.qnt_type,
+            self.zp,
+            self.scale,
+        )
+    }
+}
+
+/// Read a fixed-size name array from the runtime, which is not trusted to
+/// NUL-terminate it.
+pub(crate) fn name_from_sys(name: &[c_char]) -> String {
+    let name = unsafe { slice::from_raw_parts(name.as_ptr().cast::<u8>(), name.len()) };
+    match CStr::from_bytes_until_nul(name) {
+        Ok(name) => name.to_string_lossy().into_owned(),
+        Err(_) => String::from_utf8_lossy(name).into_owned(),
+    }
+}
+
+#[cfg(feature = "serde")]
+fn serialize_display<T: Display, S: serde::Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+impl TensorAttrView for TensorAttr {
+    fn io(&self) -> Io {
+        self.io
+    }
+
+    fn index(&self) -> u32 {
+        self.index
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dtype(&self) -> DataTypeKind {
+        self.dtype
+    }
+
+    fn num_dims(&self) -> u32 {
+        self.dims.len() as u32
+    }
+
+    fn dims(&self) -> &[u32] {
+        &self.dims
+    }
+
+    fn format(&self) -> TensorFormatKind {
+        self.fmt
+    }
+
+    fn qnt_type(&self) -> QuantTypeKind {
+        self.qnt_type
+    }
+
+    fn num_elements(&self) -> u32 {
+        self.n_elems
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn zero_point(&self) -> i32 {
+        self.zp
+    }
+
+    fn fl(&self) -> i8 {
+        self.fl
+    }
+
+    fn w_stride(&self) -> u32 {
+        self.w_stride
+    }
+
+    fn h_stride(&self) -> u32 {
+        self.h_stride
+    }
+
+    fn size(&self) -> u32 {
+        self.size
+    }
+
+    fn size_with_stride(&self) -> u32 {
+        self.size_with_stride
+    }
+
+    fn pass_through(&self) -> bool {
+        self.pass_through
+    }
+}
+
+/// Declare a query type wrapping [`TensorAttr`] for one attribute query command.
+macro_rules! tensor_attr_query {
+    ($(#[$meta:meta])* $name:ident, $cmd:ident, $io:ident, $kind:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(pub $crate::query::TensorAttr);
+
+        impl $crate::query::QueryWithInput for $name {
+            const QUERY_TYPE: rknpu2_sys::_rknn_query_cmd::Type =
+                rknpu2_sys::_rknn_query_cmd::$cmd;
+
+            type Output = rknpu2_sys::rknn_tensor_attr;
+            type Input = u32;
+
+            fn prepare(input: Self::Input, output: &mut Self::Output) {
+                output.index = input;
+            }
+        }
+
+        impl From<rknpu2_sys::rknn_tensor_attr> for $name {
+            fn from(attr: rknpu2_sys::rknn_tensor_attr) -> Self {
+                $name($crate::query::TensorAttr::from_sys(
+                    &attr,
+                    $crate::query::Io::$io,
+                    $crate::query::AttrKind::$kind,
+                ))
+            }
+        }
+
+        impl From<$name> for $crate::query::TensorAttr {
+            fn from(attr: $name) -> Self {
+                attr.0
+            }
+        }
+
+        impl $crate::query::TensorAttrView for $name {
+            fn io(&self) -> $crate::query::Io {
+                self.0.io()
+            }
+
+            fn index(&self) -> u32 {
+                self.0.index()
+            }
+
+            fn name(&self) -> &str {
+                self.0.name()
+            }
+
+            fn dtype(&self) -> $crate::tensor::DataTypeKind {
+                self.0.dtype()
+            }
+
+            fn num_dims(&self) -> u32 {
+                self.0.num_dims()
+            }
+
+            fn dims(&self) -> &[u32] {
+                self.0.dims()
+            }
+
+            fn format(&self) -> $crate::tensor::TensorFormatKind {
+                self.0.format()
+            }
+
+            fn qnt_type(&self) -> $crate::tensor::QuantTypeKind {
+                self.0.qnt_type()
+            }
+
+            fn num_elements(&self) -> u32 {
+                self.0.num_elements()
+            }
+
+            fn scale(&self) -> f32 {
+                self.0.scale()
+            }
+
+            fn zero_point(&self) -> i32 {
+                self.0.zero_point()
+            }
+
+            fn fl(&self) -> i8 {
+                self.0.fl()
+            }
+
+            fn w_stride(&self) -> u32 {
+                self.0.w_stride()
+            }
+
+            fn h_stride(&self) -> u32 {
+                self.0.h_stride()
+            }
+
+            fn size(&self) -> u32 {
+                self.0.size()
+            }
+
+            fn size_with_stride(&self) -> u32 {
+                self.0.size_with_stride()
+            }
+
+            fn pass_through(&self) -> bool {
+                self.0.pass_through()
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = $crate::query::TensorAttr;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+    };
+}
+
+pub(crate) use tensor_attr_query;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_sys_copies_reported_dims_and_name() {
+        let mut attr: rknn_tensor_attr = unsafe { std::mem::zeroed() };
+        attr.index = 2;
+        attr.n_dims = 3;
+        attr.dims[..4].copy_from_slice(&[1, 3, 224, 7]);
+        attr.name[..2].copy_from_slice(&[b'i' as _, b'n' as _]);
+        attr.pass_through = 1;
+
+        let a = TensorAttr::from_sys(&attr, Io::Input, AttrKind::Native);
+        assert_eq!(a.index(), 2);
+        assert_eq!(a.name(), "in");
+        assert_eq!(a.dims(), &[1, 3, 224]);
+        assert_eq!(a.kind(), AttrKind::Native);
+        assert!(a.pass_through());
+
+        assert_eq!(
+            TensorAttr::from_sys(&a.to_sys(), Io::Input, AttrKind::Native),
+            a
+        );
+        assert_eq!(a.clone(), a);
+        assert_ne!(a, TensorAttr::from_sys(&attr, Io::Output, AttrKind::Native));
+    }
+
+    #[test]
+    fn from_sys_reads_unterminated_name_within_bounds() {
+        let mut attr: rknn_tensor_attr = unsafe { std::mem::zeroed() };
+        attr.name.fill(b'n' as _);
+
+        let a = TensorAttr::from_sys(&attr, Io::Output, AttrKind::Plain);
+        assert_eq!(a.name().len(), attr.name.len());
+        assert!(a.name().bytes().all(|b| b == b'n'));
+    }
+}
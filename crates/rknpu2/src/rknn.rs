@@ -3,27 +3,87 @@ use rknpu2_sys::{
         RKNN_NPU_CORE_0, RKNN_NPU_CORE_0_1, RKNN_NPU_CORE_0_1_2, RKNN_NPU_CORE_1, RKNN_NPU_CORE_2,
         RKNN_NPU_CORE_ALL, RKNN_NPU_CORE_AUTO,
     },
-    rknn_context,
+    rknn_context, rknn_run_extend,
 };
 
-#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
-use crate::io::{input::IntoInputs, output::Output};
+#[cfg(feature = "rk3576")]
+use std::sync::atomic::{AtomicU32, Ordering};
 use {
     crate::{
         Error,
-        api::RKNNAPI,
-        query::{Query, QueryWithInput},
+        api::{RKNNAPI, RknnInitFlags},
+        builder::{ModelStorage, RknnBuilder},
+        io::mem::BoundMem,
+        model::{self, CompatibilityReport, Platform},
+        query::{
+            CustomString, HeapQueryWithInput, InputAttr, InputOutputNum, Io, MemSize, ModelInfo,
+            NativeInputAttr, NativeOutputAttr, OutputAttr, Query, QueryWithInput, SdkVersion,
+            TensorAttr,
+        },
+    },
+    std::{
+        collections::HashMap,
+        ffi::c_void,
+        mem,
+        os::fd::{AsRawFd, FromRawFd, OwnedFd},
+        ptr,
+        sync::{Arc, Mutex},
+        time::Duration,
     },
-    std::{ffi::c_void, ptr},
+};
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+use {
+    crate::{
+        io::{input::IntoInputs, output::Output},
+        letterbox::{self, Letterbox},
+        query::{CurrentInputAttr, CurrentOutputAttr, InputDynamicRange, TensorAttrView},
+    },
+    rknpu2_sys::{rknn_output, rknn_output_extend},
+    std::ffi::c_int,
 };
 
 /// Main rknn struct with ability to query the model and run inference.
 pub struct RKNN<A: RKNNAPI> {
     pub(crate) ctx: rknn_context,
     pub(crate) api: A,
+    /// Flags the context was created with.
+    pub(crate) flags: RknnInitFlags,
+    /// Model memory the context may still reference, shared with duplicates.
+    /// Released after [`Drop::drop`] destroys the context.
+    #[cfg_attr(not(any(feature = "rk3576", feature = "rk35xx")), allow(dead_code))]
+    pub(crate) model: Option<Arc<ModelStorage>>,
+    /// Context whose weights this one shares, destroyed after this one.
+    #[cfg_attr(not(any(feature = "rk3576", feature = "rk35xx")), allow(dead_code))]
+    pub(crate) weights: Option<Arc<dyn Send + Sync>>,
+    /// Memory of dropped
+    /// [`ZeroCopySession`](crate::io::zero_copy::ZeroCopySession)s the
+    /// context may still use, by the tensor it is bound to.
+    pub(crate) bound_mems: Mutex<HashMap<(Io, u32), BoundMem>>,
+    /// Mask last set with [`set_core_mask`](Self::set_core_mask).
+    #[cfg(feature = "rk3576")]
+    pub(crate) core_mask: AtomicU32,
+    /// Value last set with [`set_batch_core_num`](Self::set_batch_core_num).
+    #[cfg(feature = "rk3576")]
+    pub(crate) batch_core_num: AtomicU32,
 }
 
 impl<A: RKNNAPI> RKNN<A> {
+    /// Start building a context on the given backend.
+    pub fn builder<'m>(api: A) -> RknnBuilder<'m, A> {
+        RknnBuilder::new(api)
+    }
+
+    /// Check whether `model_data` is expected to load on `platform` without
+    /// initializing a context.
+    ///
+    /// See [`ModelFile::check_compatibility`](model::ModelFile::check_compatibility).
+    pub fn check_compatibility(
+        model_data: &[u8],
+        platform: Platform,
+    ) -> Result<CompatibilityReport, Error> {
+        model::check_compatibility(model_data, platform)
+    }
+
     pub fn query<T: Query>(&self) -> Result<T, Error> {
         let mut result = std::mem::MaybeUninit::<T::Output>::uninit();
         let ret = unsafe {
@@ -60,6 +120,61 @@ impl<A: RKNNAPI> RKNN<A> {
         unsafe { Ok(result.assume_init().into()) }
     }
 
+    /// Query into a zeroed heap allocation instead of the stack.
+    pub fn query_heap_with_input<T: HeapQueryWithInput>(
+        &self,
+        input: T::Input,
+    ) -> Result<T, Error> {
+        // SAFETY: implementors of `HeapQueryWithInput` guarantee all zeroes is a valid `Output`.
+        let mut result = unsafe { Box::<T::Output>::new_zeroed().assume_init() };
+        T::prepare(input, &mut result);
+
+        let ret = unsafe {
+            self.api.query(
+                self.ctx,
+                T::QUERY_TYPE,
+                &mut *result as *mut T::Output as *mut c_void,
+                std::mem::size_of::<T::Output>() as u32,
+            )?
+        };
+        if ret != 0 {
+            return Err(ret.into());
+        }
+        Ok(T::from(&result))
+    }
+
+    /// Query the input/output counts, all input and output attributes, the
+    /// SDK version, the memory usage and the custom string in one go.
+    ///
+    /// Failed memory size and custom string queries leave
+    /// [`mem_size`](ModelInfo::mem_size) and
+    /// [`custom_string`](ModelInfo::custom_string) as `None` instead of
+    /// failing the whole call.
+    pub fn model_info(&self) -> Result<ModelInfo, Error> {
+        let num = self.query::<InputOutputNum>()?;
+        let sdk = self.query::<SdkVersion>()?;
+
+        Ok(ModelInfo {
+            api_version: sdk.api_version(),
+            driver_version: sdk.driver_version(),
+            inputs: self.query_attrs::<InputAttr>(num.input_num())?,
+            outputs: self.query_attrs::<OutputAttr>(num.output_num())?,
+            native_inputs: self.query_attrs::<NativeInputAttr>(num.input_num())?,
+            native_outputs: self.query_attrs::<NativeOutputAttr>(num.output_num())?,
+            mem_size: self.query::<MemSize>().ok(),
+            custom_string: self.query::<CustomString>().ok().map(|s| s.string()),
+        })
+    }
+
+    fn query_attrs<T>(&self, count: u32) -> Result<Vec<TensorAttr>, Error>
+    where
+        T: QueryWithInput<Input = u32> + Into<TensorAttr>,
+    {
+        (0..count)
+            .map(|i| self.query_with_input::<T>(i).map(Into::into))
+            .collect()
+    }
+
     pub fn run(&self) -> Result<(), Error> {
         let ret = unsafe { self.api.run(self.ctx, ptr::null_mut())? };
         if ret != 0 {
@@ -68,6 +183,57 @@ impl<A: RKNNAPI> RKNN<A> {
         Ok(())
     }
 
+    /// Run inference, failing with [`Error::RunTimeout`] if it does not finish
+    /// within `timeout`.
+    ///
+    /// See [`RunOptions::timeout`] for the state of the context after a timeout.
+    pub fn run_with_timeout(&self, timeout: Duration) -> Result<Frame, Error> {
+        self.run_with(RunOptions::new().timeout(timeout))
+            .map(|out| out.frame)
+    }
+
+    /// Run inference with the options passed through `rknn_run_extend`.
+    ///
+    /// Fails with [`Error::ParamInvalid`] if an input fence is given but the
+    /// context was not created with
+    /// [`RknnInitFlags::with_fence_in_outside`](crate::api::RknnInitFlags::with_fence_in_outside).
+    pub fn run_with(&self, options: RunOptions) -> Result<RunOutput, Error> {
+        if options.fence_in.is_some() && !self.flags.contains(RknnInitFlags::FENCE_IN_OUTSIDE) {
+            return Err(Error::ParamInvalid);
+        }
+        let fence_in = options.fence_in.as_ref().map_or(-1, |fd| fd.as_raw_fd());
+        let mut extend = rknn_run_extend {
+            frame_id: 0,
+            non_block: options.non_block as i32,
+            timeout_ms: options.timeout_ms(),
+            fence_fd: fence_in,
+        };
+        let ret = unsafe { self.api.run(self.ctx, &mut extend)? };
+        // The input fence is only borrowed by the runtime.
+        drop(options.fence_in);
+
+        // Owned before checking `ret`, so an error return closes it.
+        let fence_out = if self.flags.contains(RknnInitFlags::FENCE_OUT_OUTSIDE)
+            && extend.fence_fd >= 0
+            && extend.fence_fd != fence_in
+        {
+            // SAFETY: with FENCE_OUT_OUTSIDE the runtime hands us a new sync-file fd.
+            Some(unsafe { OwnedFd::from_raw_fd(extend.fence_fd) })
+        } else {
+            None
+        };
+
+        let frame = Frame(extend.frame_id);
+        match ret {
+            0 => {}
+            rknpu2_sys::RKNN_ERR_TIMEOUT => {
+                return Err(Error::RunTimeout { frame_id: frame.0 });
+            }
+            _ => return Err(ret.into()),
+        }
+        Ok(RunOutput { frame, fence_out })
+    }
+
     #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
     #[cfg_attr(
         feature = "docs",
@@ -91,50 +257,467 @@ impl<A: RKNNAPI> RKNN<A> {
         Ok(())
     }
 
+    /// Change the input shapes of a dynamic-shape model, one shape per input.
+    ///
+    /// The shapes are checked against `RKNN_QUERY_INPUT_DYNAMIC_RANGE`: all
+    /// inputs must use the same entry of their supported shapes, and inputs
+    /// with a static shape must keep it. Returns the input and output
+    /// attributes for the new shapes.
+    #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+    #[cfg_attr(
+        feature = "docs",
+        doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+    )]
+    pub fn set_input_shapes(&self, shapes: &[&[u32]]) -> Result<CurrentAttrs, Error> {
+        let num = self.query::<InputOutputNum>()?;
+        if shapes.len() != num.input_num() as usize {
+            return Err(Error::SizeMismatch {
+                expected: num.input_num() as usize,
+                actual: shapes.len(),
+            });
+        }
+        let current = self.query_attrs::<CurrentInputAttr>(num.input_num())?;
+        let ranges = (0..num.input_num())
+            .map(|i| self.query_heap_with_input::<InputDynamicRange>(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        check_input_shapes(shapes, &ranges, &current)?;
+
+        let mut attrs = current
+            .into_iter()
+            .zip(&ranges)
+            .zip(shapes)
+            .map(|((attr, range), dims)| {
+                // Supported shapes are given in the format of the range.
+                let fmt = if range.shapes().is_empty() {
+                    attr.format()
+                } else {
+                    range.format()
+                };
+                attr.with_dims(dims, fmt).to_sys()
+            })
+            .collect::<Vec<_>>();
+        let ret = unsafe {
+            self.api
+                .set_input_shapes(self.ctx, attrs.len() as u32, attrs.as_mut_ptr())?
+        };
+        if ret != 0 {
+            return Err(ret.into());
+        }
+
+        Ok(CurrentAttrs {
+            inputs: self.query_attrs::<CurrentInputAttr>(num.input_num())?,
+            outputs: self.query_attrs::<CurrentOutputAttr>(num.output_num())?,
+        })
+    }
+
+    /// Pick the input shape of a dynamic-shape model to use for a `width` x
+    /// `height` image and apply it with [`set_input_shapes`](Self::set_input_shapes).
+    ///
+    /// The smallest shape of the first input that holds the image without
+    /// downscaling is used, or else the one with the closest aspect ratio.
+    /// Other inputs get the shapes at the same entry of their range. Fails
+    /// with [`Error::ParamInvalid`] if the first input has no NHWC or NCHW
+    /// dynamic range.
+    #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+    #[cfg_attr(
+        feature = "docs",
+        doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+    )]
+    pub fn select_shape_for(&self, width: u32, height: u32) -> Result<ShapeSelection, Error> {
+        let num = self.query::<InputOutputNum>()?;
+        let ranges = (0..num.input_num())
+            .map(|i| self.query_heap_with_input::<InputDynamicRange>(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        let first = ranges.first().ok_or(Error::ParamInvalid)?;
+        let entry = letterbox::choose_shape(first.shapes(), first.format(), width, height)
+            .ok_or(Error::ParamInvalid)?;
+        let (input_width, input_height) =
+            letterbox::image_size(&first.shapes()[entry], first.format())
+                .ok_or(Error::ParamInvalid)?;
+
+        let current = self.query_attrs::<CurrentInputAttr>(num.input_num())?;
+        let shapes = ranges
+            .iter()
+            .zip(&current)
+            .map(|(range, attr)| range.shapes().get(entry).map_or(attr.dims(), Vec::as_slice))
+            .collect::<Vec<_>>();
+
+        Ok(ShapeSelection {
+            attrs: self.set_input_shapes(&shapes)?,
+            letterbox: Letterbox::new(width, height, input_width, input_height),
+        })
+    }
+
     #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
     #[cfg_attr(
         feature = "docs",
         doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
     )]
     pub fn get_outputs<'a>(&self, outputs: &mut [Output<'a>]) -> Result<(), Error> {
+        self.outputs_get(outputs, ptr::null_mut())
+    }
+
+    /// Submit an inference without waiting for it to finish.
+    ///
+    /// The context has to be created with [`RknnInitFlags::with_async`](crate::api::RknnInitFlags::with_async)
+    /// for the NPU to run in the background; the returned frame is then
+    /// passed to [`get_outputs_for`](Self::get_outputs_for).
+    #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+    #[cfg_attr(
+        feature = "docs",
+        doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+    )]
+    pub fn run_async(&self) -> Result<Frame, Error> {
+        self.run_with(RunOptions::new().non_blocking())
+            .map(|out| out.frame)
+    }
+
+    /// Wait for `frame` to finish and fill `outputs` with its results.
+    ///
+    /// Results of older frames that were never collected are released and
+    /// skipped. Fails with [`Error::FrameUnavailable`] if the runtime has
+    /// already moved past `frame`, or stops returning newer frames before
+    /// reaching it.
+    #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+    #[cfg_attr(
+        feature = "docs",
+        doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+    )]
+    pub fn get_outputs_for<'a>(
+        &self,
+        frame: Frame,
+        outputs: &mut [Output<'a>],
+    ) -> Result<(), Error> {
+        let mut last = 0;
+        loop {
+            let mut extend = rknn_output_extend { frame_id: 0 };
+            // Fresh from `outputs` on every attempt, so skipped frames leave
+            // runtime-allocated outputs untouched.
+            let mut outputs_ffi = outputs
+                .iter_mut()
+                .map(|t| t.as_sys_output())
+                .collect::<Vec<_>>();
+            self.outputs_get_raw(&mut outputs_ffi, &mut extend)?;
+
+            let received = extend.frame_id;
+            match received.cmp(&frame.0) {
+                std::cmp::Ordering::Less => {
+                    let ret = unsafe {
+                        self.api.outputs_release(
+                            self.ctx,
+                            outputs_ffi.len() as u32,
+                            outputs_ffi.as_mut_ptr(),
+                        )?
+                    };
+                    if ret != 0 {
+                        return Err(ret.into());
+                    }
+                    if received <= last {
+                        return Err(Error::FrameUnavailable {
+                            requested: frame.0,
+                            received,
+                        });
+                    }
+                    last = received;
+                }
+                std::cmp::Ordering::Equal => {
+                    for (output, ffi) in outputs.iter_mut().zip(outputs_ffi.iter()) {
+                        output.from_sys_output(ffi);
+                    }
+                    return Ok(());
+                }
+                std::cmp::Ordering::Greater => {
+                    return Err(Error::FrameUnavailable {
+                        requested: frame.0,
+                        received,
+                    });
+                }
+            }
+        }
+    }
+
+    #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+    fn outputs_get(
+        &self,
+        outputs: &mut [Output<'_>],
+        extend: *mut rknn_output_extend,
+    ) -> Result<(), Error> {
         let mut outputs_ffi = outputs
             .iter_mut()
             .map(|t| t.as_sys_output())
             .collect::<Vec<_>>();
 
+        let result = self.outputs_get_raw(&mut outputs_ffi, extend);
+
+        for (output, ffi) in outputs.iter_mut().zip(outputs_ffi.iter_mut()) {
+            output.from_sys_output(ffi);
+        }
+
+        result
+    }
+
+    #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+    fn outputs_get_raw(
+        &self,
+        outputs_ffi: &mut [rknn_output],
+        extend: *mut rknn_output_extend,
+    ) -> Result<(), Error> {
         let ret = unsafe {
             self.api.outputs_get(
                 self.ctx,
                 outputs_ffi.len() as u32,
                 outputs_ffi.as_mut_ptr(),
-                std::ptr::null_mut(),
+                extend,
             )?
         };
+        if ret != 0 {
+            return Err(ret.into());
+        }
+        Ok(())
+    }
 
-        for (output, ffi) in outputs.iter_mut().zip(outputs_ffi.iter_mut()) {
-            output.from_sys_output(ffi);
+    /// Create a second context that shares this one's weights through
+    /// `rknn_dup_context`.
+    ///
+    /// The duplicate has its own inputs and outputs and can be moved to another
+    /// thread; each handle destroys its own context when dropped.
+    #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+    #[cfg_attr(
+        feature = "docs",
+        doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+    )]
+    pub fn try_duplicate(&self) -> Result<RKNN<A>, Error>
+    where
+        A: Clone,
+    {
+        let mut ctx_in = self.ctx;
+        let mut ctx_out: rknn_context = 0;
+        let ret = unsafe { self.api.dup_context(&mut ctx_in, &mut ctx_out)? };
+        if ret != 0 {
+            return Err(ret.into());
+        }
+        Ok(RKNN {
+            ctx: ctx_out,
+            api: self.api.clone(),
+            flags: self.flags,
+            model: self.model.clone(),
+            weights: self.weights.clone(),
+            bound_mems: Mutex::default(),
+            #[cfg(feature = "rk3576")]
+            core_mask: AtomicU32::new(NpuCores::auto().bits()),
+            #[cfg(feature = "rk3576")]
+            batch_core_num: AtomicU32::new(1),
+        })
+    }
+
+    /// Pin the context to `mask`.
+    ///
+    /// Fails with [`Error::ParamInvalid`] if the mask has fewer cores than
+    /// were set with [`set_batch_core_num`](Self::set_batch_core_num), and
+    /// with the runtime's error if `rknn_set_core_mask` rejects the mask,
+    /// e.g. a core the platform does not have. The context keeps its
+    /// previous mask in both cases.
+    #[cfg(feature = "rk3576")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "rk3576")))]
+    pub fn set_core_mask(&self, mask: NpuCores) -> Result<(), Error> {
+        if mask
+            .pinned_core_count()
+            .is_some_and(|n| n < self.batch_core_num.load(Ordering::Relaxed))
+        {
+            return Err(Error::ParamInvalid);
         }
 
+        let ret = unsafe { self.api.set_core_mask(self.ctx, mask.into())? };
         if ret != 0 {
             return Err(ret.into());
         }
 
+        self.core_mask.store(mask.bits(), Ordering::Relaxed);
         Ok(())
     }
 
+    /// Mask last set with [`set_core_mask`](Self::set_core_mask),
+    /// [`NpuCores::auto`] if none was set.
     #[cfg(feature = "rk3576")]
     #[cfg_attr(feature = "docs", doc(cfg(feature = "rk3576")))]
-    pub fn set_core_mask(&self, mask: NpuCores) -> Result<(), Error> {
-        unsafe {
-            self.api.set_core_mask(self.ctx, mask.into())?;
+    pub fn core_mask(&self) -> NpuCores {
+        NpuCores::from_bits_retain(self.core_mask.load(Ordering::Relaxed))
+    }
+
+    /// Split each batch of a multi-batch model across `core_num` NPU cores.
+    ///
+    /// Fails with [`Error::ParamInvalid`] if `core_num` is 0, exceeds
+    /// [`NpuCores::PLATFORM_CORES`], or exceeds the cores in the current
+    /// [`core_mask`](Self::core_mask). `PLATFORM_CORES` is only an upper
+    /// bound, so on RK3576 a `core_num` of 3 passes this check and is
+    /// rejected by the driver instead.
+    #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+    #[cfg_attr(
+        feature = "docs",
+        doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+    )]
+    pub fn set_batch_core_num(&self, core_num: u32) -> Result<(), Error> {
+        if core_num == 0 || core_num > NpuCores::PLATFORM_CORES {
+            return Err(Error::ParamInvalid);
+        }
+        #[cfg(feature = "rk3576")]
+        if self
+            .core_mask()
+            .pinned_core_count()
+            .is_some_and(|n| core_num > n)
+        {
+            return Err(Error::ParamInvalid);
         }
 
+        let ret = unsafe { self.api.set_batch_core_num(self.ctx, core_num as c_int)? };
+        if ret != 0 {
+            return Err(ret.into());
+        }
+
+        #[cfg(feature = "rk3576")]
+        self.batch_core_num.store(core_num, Ordering::Relaxed);
         Ok(())
     }
 }
 
+/// Options for [`RKNN::run_with`].
+#[derive(Debug, Default)]
+pub struct RunOptions {
+    non_block: bool,
+    timeout: Option<Duration>,
+    fence_in: Option<OwnedFd>,
+}
+
+impl RunOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return as soon as the inference is submitted. Only has an effect on
+    /// contexts created with [`RknnInitFlags::with_async`](crate::api::RknnInitFlags::with_async).
+    pub fn non_blocking(mut self) -> Self {
+        self.non_block = true;
+        self
+    }
+
+    /// Give up waiting for the NPU after `timeout`, rounded up to whole
+    /// milliseconds. Without a timeout the runtime's default is used.
+    ///
+    /// The runtime does not cancel the timed-out job. The context stays valid
+    /// and can be run again, but the outputs of the timed-out frame should not
+    /// be read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sync-file fence the NPU waits on before starting, e.g. one signalled by
+    /// RGA or the VPU. Requires
+    /// [`RknnInitFlags::with_fence_in_outside`](crate::api::RknnInitFlags::with_fence_in_outside).
+    ///
+    /// The fd stays owned by these options and is closed once `rknn_run`
+    /// returns.
+    pub fn fence_in(mut self, fence: OwnedFd) -> Self {
+        self.fence_in = Some(fence);
+        self
+    }
+
+    fn timeout_ms(&self) -> i32 {
+        match self.timeout {
+            // 0 selects the runtime default, so never round down to it.
+            Some(t) => i32::try_from(t.as_nanos().div_ceil(1_000_000))
+                .unwrap_or(i32::MAX)
+                .max(1),
+            None => 0,
+        }
+    }
+}
+
+/// Input and output attributes after [`RKNN::set_input_shapes`].
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[cfg_attr(
+    feature = "docs",
+    doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrentAttrs {
+    pub inputs: Vec<TensorAttr>,
+    pub outputs: Vec<TensorAttr>,
+}
+
+/// Result of [`RKNN::select_shape_for`].
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[cfg_attr(
+    feature = "docs",
+    doc(cfg(any(feature = "rk35xx", feature = "rk3576")))
+)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeSelection {
+    /// How to fit the image into the selected shape.
+    pub letterbox: Letterbox,
+    pub attrs: CurrentAttrs,
+}
+
+/// Check that `shapes` select one entry of every input's dynamic range.
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+fn check_input_shapes(
+    shapes: &[&[u32]],
+    ranges: &[InputDynamicRange],
+    current: &[TensorAttr],
+) -> Result<(), Error> {
+    let count = ranges.iter().map(|r| r.shapes().len()).max().unwrap_or(0);
+    let mut candidates = (0..count).collect::<Vec<_>>();
+
+    for ((&dims, range), attr) in shapes.iter().zip(ranges).zip(current) {
+        let supported = range.shapes();
+        let unsupported = |supported: &[Vec<u32>]| Error::UnsupportedInputShape {
+            index: attr.index(),
+            requested: dims.to_vec(),
+            supported: supported.to_vec(),
+        };
+
+        if supported.is_empty() {
+            if dims != attr.dims() {
+                return Err(unsupported(&[attr.dims().to_vec()]));
+            }
+            continue;
+        }
+        candidates.retain(|&i| supported.get(i).is_some_and(|s| s == dims));
+        if candidates.is_empty() {
+            return Err(unsupported(supported));
+        }
+    }
+    Ok(())
+}
+
+/// Result of [`RKNN::run_with`].
+#[derive(Debug)]
+pub struct RunOutput {
+    pub frame: Frame,
+    /// Sync-file fence signalled when the outputs are ready. Only returned by
+    /// contexts created with
+    /// [`RknnInitFlags::with_fence_out_outside`](crate::api::RknnInitFlags::with_fence_out_outside);
+    /// the caller owns it.
+    pub fence_out: Option<OwnedFd>,
+}
+
+/// An inference submitted with [`RKNN::run_with`] or [`RKNN::run_async`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Frame(u64);
+
+impl Frame {
+    /// Frame id assigned by the runtime.
+    pub const fn id(self) -> u64 {
+        self.0
+    }
+}
+
 impl<A: RKNNAPI> Drop for RKNN<A> {
     fn drop(&mut self) {
+        let bound_mems = mem::take(self.bound_mems.get_mut().unwrap_or_else(|e| e.into_inner()));
+        for mem in bound_mems.into_values() {
+            mem.destroy(self);
+        }
         unsafe {
             self.api.destroy(self.ctx).unwrap();
         }
@@ -146,6 +729,7 @@ use bitflags::bitflags;
 bitflags! {
     /// Flags for specifying which NPU cores to use.
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub struct NpuCores: u32 {
         // Single cores
         const CORE0 = RKNN_NPU_CORE_0;
@@ -162,6 +746,26 @@ bitflags! {
 }
 
 impl NpuCores {
+    /// Upper bound on the NPU cores of the platforms the crate was built for,
+    /// not the core count of the running SoC.
+    ///
+    /// `rk3576` covers both RK3588 (3 cores) and RK3576 (2 cores), and the
+    /// runtime has no query for the core count, so this is 3 for that
+    /// feature; `rk35xx` (RK356x) has a single core.
+    #[cfg(feature = "rk3576")]
+    pub const PLATFORM_CORES: u32 = 3;
+    #[cfg(all(feature = "rk35xx", not(feature = "rk3576")))]
+    pub const PLATFORM_CORES: u32 = 1;
+
+    /// Number of cores the mask pins to, `None` for [`auto`](Self::auto) and
+    /// [`ALL`](Self::ALL) which leave the choice to the driver.
+    pub fn pinned_core_count(self) -> Option<u32> {
+        if self.is_empty() || self.contains(Self::ALL) {
+            return None;
+        }
+        Some((self & Self::CORE0_1_2).bits().count_ones())
+    }
+
     /// Let the driver choose cores automatically.
     pub const fn auto() -> Self {
         // This is equivalent to `Self::empty()`
@@ -216,3 +820,85 @@ impl From<u32> for NpuCores {
         NpuCores::from_bits_truncate(bits)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinned_core_count() {
+        assert_eq!(NpuCores::auto().pinned_core_count(), None);
+        assert_eq!(NpuCores::ALL.pinned_core_count(), None);
+        assert_eq!(NpuCores::CORE2.pinned_core_count(), Some(1));
+        assert_eq!(NpuCores::CORE0_1.pinned_core_count(), Some(2));
+        assert_eq!(NpuCores::CORE0_1_2.pinned_core_count(), Some(3));
+    }
+
+    #[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+    #[test]
+    fn input_shapes_must_share_a_range_entry() {
+        use {
+            crate::query::AttrKind,
+            rknpu2_sys::{rknn_input_range, rknn_tensor_attr},
+        };
+
+        let range = |index: u32, shapes: &[[u32; 4]]| {
+            let mut range: Box<rknn_input_range> = unsafe { Box::new_zeroed().assume_init() };
+            range.index = index;
+            range.shape_number = shapes.len() as u32;
+            range.n_dims = 4;
+            for (dst, src) in range.dyn_range.iter_mut().zip(shapes) {
+                dst[..4].copy_from_slice(src);
+            }
+            InputDynamicRange::from(&*range)
+        };
+        let attr = |index: u32, dims: &[u32]| {
+            let mut attr: rknn_tensor_attr = unsafe { std::mem::zeroed() };
+            attr.index = index;
+            attr.n_dims = dims.len() as u32;
+            attr.dims[..dims.len()].copy_from_slice(dims);
+            TensorAttr::from_sys(&attr, crate::query::Io::Input, AttrKind::Current)
+        };
+
+        let ranges = [
+            range(0, &[[1, 224, 224, 3], [1, 320, 320, 3]]),
+            range(1, &[[1, 7, 7, 1], [1, 10, 10, 1]]),
+            range(2, &[]),
+        ];
+        let current = [
+            attr(0, &[1, 224, 224, 3]),
+            attr(1, &[1, 7, 7, 1]),
+            attr(2, &[1, 4]),
+        ];
+
+        check_input_shapes(
+            &[&[1, 320, 320, 3], &[1, 10, 10, 1], &[1, 4]],
+            &ranges,
+            &current,
+        )
+        .unwrap();
+
+        // Each shape is supported, but not in the same combination.
+        let err = check_input_shapes(
+            &[&[1, 320, 320, 3], &[1, 7, 7, 1], &[1, 4]],
+            &ranges,
+            &current,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnsupportedInputShape { index: 1, ref requested, .. } if requested == &[1, 7, 7, 1]
+        ));
+
+        let err = check_input_shapes(
+            &[&[1, 224, 224, 3], &[1, 7, 7, 1], &[1, 8]],
+            &ranges,
+            &current,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnsupportedInputShape { index: 2, ref supported, .. } if supported == &[vec![1, 4]]
+        ));
+    }
+}
@@ -13,6 +13,7 @@ use {
             RKNN_TENSOR_UINT32,
         },
     },
+    std::fmt::Display,
 };
 
 #[derive(Debug)]
@@ -62,6 +63,20 @@ impl From<TensorFormatKind> for _rknn_tensor_format::Type {
     }
 }
 
+/// Same names as `get_format_string` in rknn_api.h.
+impl Display for TensorFormatKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TensorFormatKind::NCHW(_) => "NCHW",
+            TensorFormatKind::NHWC(_) => "NHWC",
+            TensorFormatKind::NC1HWC2(_) => "NC1HWC2",
+            TensorFormatKind::UNDEFINED(_) => "UNDEFINED",
+            TensorFormatKind::Max(_) | TensorFormatKind::Other(_) => "UNKNOW",
+        };
+        f.write_str(name)
+    }
+}
+
 pub struct DataType;
 
 impl DataType {
@@ -140,6 +155,28 @@ impl From<DataTypeKind> for _rknn_tensor_type::Type {
     }
 }
 
+/// Same names as `get_type_string` in rknn_api.h.
+impl Display for DataTypeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            DataTypeKind::Float32(_) => "FP32",
+            DataTypeKind::Float16(_) => "FP16",
+            DataTypeKind::BFloat16(_) => "BF16",
+            DataTypeKind::Int4(_) => "INT4",
+            DataTypeKind::Int8(_) => "INT8",
+            DataTypeKind::UInt8(_) => "UINT8",
+            DataTypeKind::Int16(_) => "INT16",
+            DataTypeKind::UInt16(_) => "UINT16",
+            DataTypeKind::Int32(_) => "INT32",
+            DataTypeKind::UInt32(_) => "UINT32",
+            DataTypeKind::Int64(_) => "INT64",
+            DataTypeKind::Bool(_) => "BOOL",
+            DataTypeKind::Max(_) | DataTypeKind::Other(_) => "UNKNOW",
+        };
+        f.write_str(name)
+    }
+}
+
 #[derive(Debug)]
 pub struct QuantType;
 
@@ -182,10 +219,40 @@ impl From<_rknn_tensor_qnt_type::Type> for QuantTypeKind {
     }
 }
 
-pub trait TensorType: Sized + Default {
+/// Same names as `get_qnt_type_string` in rknn_api.h.
+impl Display for QuantTypeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            QuantTypeKind::None(_) => "NONE",
+            QuantTypeKind::Dfp(_) => "DFP",
+            QuantTypeKind::AffineAsymmetric(_) => "AFFINE",
+            QuantTypeKind::Other(_) => "UNKNOW",
+        };
+        f.write_str(name)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Element type of a tensor.
+///
+/// Sealed: tensor memory is viewed as slices of these types, so only the
+/// built-in plain numeric types, for which every bit pattern is valid and
+/// which have no padding, implement it.
+pub trait TensorType: sealed::Sealed + Sized + Default {
     const TYPE: _rknn_tensor_type::Type;
 }
 
+macro_rules! sealed {
+    ($($ty:ty),*) => {
+        $(impl sealed::Sealed for $ty {})*
+    };
+}
+
+sealed!(f32, f16, bf16, u8, i8, i32, u32, i16, u16, i64);
+
 impl TensorType for f32 {
     const TYPE: _rknn_tensor_type::Type = _rknn_tensor_type::RKNN_TENSOR_FLOAT32;
 }
@@ -0,0 +1,565 @@
+//! Backend that never touches an NPU, shared by the host-side tests.
+#![allow(dead_code)]
+
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+use std::ffi::c_char;
+use {
+    rknpu2::{Error, RKNN, api::RKNNAPI},
+    rknpu2_sys::*,
+    std::{
+        collections::VecDeque,
+        ffi::{c_int, c_void},
+        fs::File,
+        os::fd::{IntoRawFd, RawFd},
+        sync::{
+            Arc, Condvar, Mutex,
+            atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering},
+        },
+        time::Duration,
+    },
+};
+
+/// Arguments of an `init` call.
+pub struct Init {
+    pub model: usize,
+    pub size: u32,
+    pub flags: u32,
+    pub extend: Option<rknn_init_extend>,
+}
+
+/// Calls recorded by [`FakeApi`] and knobs for its behavior.
+#[derive(Default)]
+pub struct FakeState {
+    /// Every `init` call, in context order.
+    pub inits: Mutex<Vec<Init>>,
+    /// Contexts in the order they were destroyed.
+    pub destroyed: Mutex<Vec<rknn_context>>,
+    pub started: AtomicUsize,
+    pub runs: AtomicUsize,
+    /// Returned by `run`.
+    pub run_ret: AtomicI32,
+    /// Make `run` return a new fd as its output fence.
+    pub fence_out: AtomicBool,
+    /// Fds returned as output fences.
+    pub fences: Mutex<Vec<RawFd>>,
+    /// Frames run but not yet collected by `outputs_get`.
+    pub pending: Mutex<VecDeque<u64>>,
+    /// `is_prealloc` of each output, for every `outputs_get` call.
+    pub output_gets: Mutex<Vec<Vec<u8>>>,
+    pub releases: AtomicUsize,
+    /// `run` blocks while this is set.
+    pub gate: (Mutex<bool>, Condvar),
+    pub allocated: AtomicUsize,
+    pub mems_destroyed: AtomicUsize,
+    /// Mode of every `mem_sync` call.
+    pub synced: Mutex<Vec<rknn_mem_sync_mode>>,
+    /// Bytes to shift the next allocation's CPU address by.
+    pub shift: AtomicUsize,
+    pub alloc_flags: Mutex<Vec<u64>>,
+    /// Mask of every `set_core_mask` call.
+    pub core_masks: Mutex<Vec<rknn_core_mask>>,
+    /// Returned by `set_core_mask`.
+    pub core_mask_ret: AtomicI32,
+    /// Type and memory of every `set_io_mem` call.
+    pub bound: Mutex<Vec<(rknn_tensor_type, usize)>>,
+}
+
+impl FakeState {
+    /// Make `run` block until [`open`](Self::open) is called.
+    pub fn close(&self) {
+        *self.gate.0.lock().unwrap() = true;
+    }
+
+    pub fn open(&self) {
+        *self.gate.0.lock().unwrap() = false;
+        self.gate.1.notify_all();
+    }
+
+    pub fn wait_started(&self, n: usize) {
+        while self.started.load(Ordering::SeqCst) < n {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct FakeApi {
+    pub state: Arc<FakeState>,
+}
+
+impl FakeApi {
+    fn alloc(&self, size: u64) -> *mut rknn_tensor_mem {
+        if size == 0 {
+            return std::ptr::null_mut();
+        }
+        let words = vec![0u64; size as usize / 8 + 2].into_boxed_slice();
+        let base = Box::into_raw(words);
+        let shift = self.state.shift.swap(0, Ordering::SeqCst);
+        self.state.allocated.fetch_add(1, Ordering::SeqCst);
+        Box::into_raw(Box::new(rknn_tensor_mem {
+            virt_addr: unsafe { (base as *mut u8).add(shift) } as *mut c_void,
+            phys_addr: 0x1000,
+            fd: 3,
+            offset: 0,
+            size: size as u32,
+            flags: 0,
+            priv_data: base as *mut c_void,
+        }))
+    }
+
+    /// Double the unpadded input pixels of a [`ZeroCopySession`] into each
+    /// of its outputs.
+    ///
+    /// [`ZeroCopySession`]: rknpu2::io::zero_copy::ZeroCopySession
+    fn run_bound(&self) {
+        let bound = self.state.bound.lock().unwrap();
+        if bound.is_empty() {
+            return;
+        }
+        let (_, input) = bound
+            .iter()
+            .find(|(t, _)| *t == _rknn_tensor_type::RKNN_TENSOR_INT8)
+            .unwrap();
+        let input = unsafe {
+            let mem = &*(*input as *mut rknn_tensor_mem);
+            std::slice::from_raw_parts(mem.virt_addr as *const i8, 8)
+        };
+        let outputs = bound
+            .iter()
+            .filter(|(t, _)| *t == _rknn_tensor_type::RKNN_TENSOR_FLOAT32);
+        for (_, mem) in outputs {
+            let output = unsafe {
+                let mem = &*(*mem as *mut rknn_tensor_mem);
+                std::slice::from_raw_parts_mut(mem.virt_addr as *mut f32, 4)
+            };
+            for (i, out) in output.iter_mut().enumerate() {
+                *out = input[i / 2 * 4 + i % 2] as f32 * 2.0;
+            }
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+impl RKNNAPI for FakeApi {
+    unsafe fn init(
+        &self,
+        context: *mut rknn_context,
+        model: *mut c_void,
+        size: u32,
+        flag: u32,
+        extend: *mut rknn_init_extend,
+    ) -> Result<c_int, Error> {
+        let mut inits = self.state.inits.lock().unwrap();
+        inits.push(Init {
+            model: model as usize,
+            size,
+            flags: flag,
+            extend: unsafe { extend.as_ref() }.copied(),
+        });
+        // Contexts are numbered from 1.
+        unsafe { *context = inits.len() as rknn_context };
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn dup_context(
+        &self,
+        _context_in: *mut rknn_context,
+        _context_out: *mut rknn_context,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    unsafe fn destroy(&self, context: rknn_context) -> Result<c_int, Error> {
+        self.state.destroyed.lock().unwrap().push(context);
+        Ok(0)
+    }
+
+    unsafe fn query(
+        &self,
+        _context: rknn_context,
+        cmd: rknn_query_cmd,
+        info: *mut c_void,
+        _size: u32,
+    ) -> Result<c_int, Error> {
+        match cmd {
+            _rknn_query_cmd::RKNN_QUERY_IN_OUT_NUM => unsafe {
+                let num = &mut *(info as *mut rknn_input_output_num);
+                num.n_input = 1;
+                num.n_output = 2;
+            },
+            _rknn_query_cmd::RKNN_QUERY_OUTPUT_ATTR => unsafe {
+                let attr = &mut *(info as *mut rknn_tensor_attr);
+                attr.n_elems = 4;
+            },
+            // 2x2 int8 image with each row padded to 4 bytes.
+            _rknn_query_cmd::RKNN_QUERY_NATIVE_INPUT_ATTR => unsafe {
+                let attr = &mut *(info as *mut rknn_tensor_attr);
+                attr.n_dims = 4;
+                attr.dims[..4].copy_from_slice(&[1, 2, 2, 1]);
+                attr.n_elems = 4;
+                attr.size = 4;
+                attr.w_stride = 4;
+                attr.size_with_stride = 8;
+                attr.fmt = _rknn_tensor_format::RKNN_TENSOR_NHWC;
+                attr.type_ = _rknn_tensor_type::RKNN_TENSOR_INT8;
+            },
+            _rknn_query_cmd::RKNN_QUERY_NATIVE_NHWC_OUTPUT_ATTR => unsafe {
+                let attr = &mut *(info as *mut rknn_tensor_attr);
+                attr.n_dims = 2;
+                attr.dims[..2].copy_from_slice(&[1, 4]);
+                attr.n_elems = 4;
+                attr.size = 16;
+                attr.size_with_stride = 16;
+                attr.fmt = _rknn_tensor_format::RKNN_TENSOR_NHWC;
+                attr.type_ = _rknn_tensor_type::RKNN_TENSOR_FLOAT32;
+            },
+            _ => return Ok(rknpu2_sys::RKNN_ERR_PARAM_INVALID),
+        }
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn inputs_set(
+        &self,
+        _context: rknn_context,
+        _n_inputs: u32,
+        _inputs: *mut rknn_input,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn set_batch_core_num(
+        &self,
+        _context: rknn_context,
+        _core_num: c_int,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn set_core_mask(
+        &self,
+        _context: rknn_context,
+        core_mask: rknn_core_mask,
+    ) -> Result<c_int, Error> {
+        self.state.core_masks.lock().unwrap().push(core_mask);
+        Ok(self.state.core_mask_ret.load(Ordering::SeqCst))
+    }
+
+    unsafe fn run(
+        &self,
+        _context: rknn_context,
+        extend: *mut rknn_run_extend,
+    ) -> Result<c_int, Error> {
+        self.state.started.fetch_add(1, Ordering::SeqCst);
+        let (lock, cvar) = &self.state.gate;
+        let _open = cvar
+            .wait_while(lock.lock().unwrap(), |closed| *closed)
+            .unwrap();
+        self.run_bound();
+        let frame_id = self.state.runs.fetch_add(1, Ordering::SeqCst) as u64 + 1;
+        self.state.pending.lock().unwrap().push_back(frame_id);
+        if let Some(extend) = unsafe { extend.as_mut() } {
+            extend.frame_id = frame_id;
+            if self.state.fence_out.load(Ordering::SeqCst) {
+                let fence = File::open("/dev/null").unwrap().into_raw_fd();
+                self.state.fences.lock().unwrap().push(fence);
+                extend.fence_fd = fence;
+            }
+        }
+        Ok(self.state.run_ret.load(Ordering::SeqCst))
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn outputs_get(
+        &self,
+        _context: rknn_context,
+        n_outputs: u32,
+        outputs: *mut rknn_output,
+        extend: *mut rknn_output_extend,
+    ) -> Result<c_int, Error> {
+        // Frame 0 when nothing is pending, like a runtime without frame ids.
+        let frame_id = self.state.pending.lock().unwrap().pop_front();
+        if let Some(extend) = unsafe { extend.as_mut() } {
+            extend.frame_id = frame_id.unwrap_or(0);
+        }
+
+        let outputs = unsafe { std::slice::from_raw_parts_mut(outputs, n_outputs as usize) };
+        let prealloc = outputs.iter().map(|output| output.is_prealloc).collect();
+        self.state.output_gets.lock().unwrap().push(prealloc);
+        for output in outputs {
+            if output.is_prealloc == 0 {
+                output.buf = Box::into_raw(Box::new([0f32; 4])) as *mut c_void;
+                output.size = 16;
+            }
+            let len = output.size as usize / std::mem::size_of::<f32>();
+            let buf = unsafe { std::slice::from_raw_parts_mut(output.buf as *mut f32, len) };
+            buf.fill(output.index as f32);
+        }
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn outputs_release(
+        &self,
+        _context: rknn_context,
+        n_outputs: u32,
+        outputs: *mut rknn_output,
+    ) -> Result<c_int, Error> {
+        let outputs = unsafe { std::slice::from_raw_parts_mut(outputs, n_outputs as usize) };
+        for output in outputs.iter().filter(|output| output.is_prealloc == 0) {
+            drop(unsafe { Box::from_raw(output.buf as *mut [f32; 4]) });
+        }
+        self.state.releases.fetch_add(1, Ordering::SeqCst);
+        Ok(0)
+    }
+    unsafe fn create_mem_from_phys(
+        &self,
+        _ctx: rknn_context,
+        _phys_addr: u64,
+        _virt_addr: *mut c_void,
+        _size: u32,
+    ) -> Result<*mut rknn_tensor_mem, Error> {
+        Err(Error::Fail)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576", feature = "rv110x"))]
+    unsafe fn create_mem_from_fd(
+        &self,
+        _ctx: rknn_context,
+        fd: i32,
+        virt_addr: *mut c_void,
+        size: u32,
+        offset: i32,
+    ) -> Result<*mut rknn_tensor_mem, Error> {
+        self.state.allocated.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::into_raw(Box::new(rknn_tensor_mem {
+            virt_addr,
+            phys_addr: 0,
+            fd,
+            offset,
+            size,
+            flags: 0,
+            priv_data: std::ptr::null_mut(),
+        })))
+    }
+
+    unsafe fn create_mem(
+        &self,
+        _ctx: rknn_context,
+        size: u32,
+    ) -> Result<*mut rknn_tensor_mem, Error> {
+        Ok(self.alloc(size as u64))
+    }
+
+    unsafe fn create_mem2(
+        &self,
+        _ctx: rknn_context,
+        size: u64,
+        alloc_flags: u64,
+    ) -> Result<*mut rknn_tensor_mem, Error> {
+        self.state.alloc_flags.lock().unwrap().push(alloc_flags);
+        Ok(self.alloc(size))
+    }
+
+    unsafe fn destroy_mem(
+        &self,
+        _ctx: rknn_context,
+        mem: *mut rknn_tensor_mem,
+    ) -> Result<c_int, Error> {
+        let mem = unsafe { Box::from_raw(mem) };
+        // Imported memory has no heap buffer.
+        if !mem.priv_data.is_null() {
+            let words = mem.size as usize / 8 + 2;
+            drop(unsafe {
+                Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                    mem.priv_data as *mut u64,
+                    words,
+                ))
+            });
+        }
+        self.state.mems_destroyed.fetch_add(1, Ordering::SeqCst);
+        Ok(0)
+    }
+
+    unsafe fn set_weight_mem(
+        &self,
+        _ctx: rknn_context,
+        _mem: *mut rknn_tensor_mem,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    unsafe fn set_internal_mem(
+        &self,
+        _ctx: rknn_context,
+        _mem: *mut rknn_tensor_mem,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    unsafe fn set_io_mem(
+        &self,
+        _ctx: rknn_context,
+        mem: *mut rknn_tensor_mem,
+        attr: *mut rknn_tensor_attr,
+    ) -> Result<c_int, Error> {
+        let dtype = unsafe { (*attr).type_ };
+        self.state.bound.lock().unwrap().push((dtype, mem as usize));
+        Ok(0)
+    }
+
+    unsafe fn set_input_shape(
+        &self,
+        _ctx: rknn_context,
+        _attr: *mut rknn_tensor_attr,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn set_input_shapes(
+        &self,
+        _ctx: rknn_context,
+        _n_inputs: u32,
+        _attr: *mut rknn_tensor_attr,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    unsafe fn mem_sync(
+        &self,
+        _context: rknn_context,
+        _mem: *mut rknn_tensor_mem,
+        mode: rknn_mem_sync_mode,
+    ) -> Result<c_int, Error> {
+        self.state.synced.lock().unwrap().push(mode);
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn matmul_create(
+        &self,
+        _ctx: *mut rknn_matmul_ctx,
+        _info: *mut rknn_matmul_info,
+        _io_attr: *mut rknn_matmul_io_attr,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn matmul_create_dynamic_shape(
+        &self,
+        _ctx: *mut rknn_matmul_ctx,
+        _info: *mut rknn_matmul_info,
+        _shape_num: c_int,
+        _dynamic_shapes: *mut rknn_matmul_shape,
+        _io_attrs: *mut rknn_matmul_io_attr,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn matmul_set_io_mem(
+        &self,
+        _ctx: rknn_matmul_ctx,
+        _mem: *mut rknn_tensor_mem,
+        _attr: *mut rknn_matmul_tensor_attr,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn matmul_set_core_mask(
+        &self,
+        _ctx: rknn_matmul_ctx,
+        _core_mask: rknn_core_mask,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn matmul_set_quant_params(
+        &self,
+        _ctx: rknn_matmul_ctx,
+        _params: *mut rknn_quant_params,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn matmul_get_quant_params(
+        &self,
+        _ctx: rknn_matmul_ctx,
+        _params: *mut rknn_quant_params,
+        _scale: *mut f32,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn matmul_set_dynamic_shape(
+        &self,
+        _ctx: rknn_matmul_ctx,
+        _shape: *mut rknn_matmul_shape,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn matmul_run(&self, _ctx: rknn_matmul_ctx) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn matmul_destroy(&self, _ctx: rknn_matmul_ctx) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn B_normal_layout_to_native_layout(
+        &self,
+        _B_input: *mut c_void,
+        _B_output: *mut c_void,
+        _K: c_int,
+        _N: c_int,
+        _info: *mut rknn_matmul_info,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn register_custom_ops(
+        &self,
+        _ctx: rknn_context,
+        _ops: *mut rknn_custom_op,
+        _custom_op_num: u32,
+    ) -> Result<c_int, Error> {
+        Ok(0)
+    }
+
+    #[cfg(any(feature = "rk35xx", feature = "rk3576"))]
+    unsafe fn custom_op_get_op_attr(
+        &self,
+        _op_ctx: *mut rknn_custom_op_context,
+        _attr_name: *const c_char,
+        _op_attr: *mut rknn_custom_op_attr,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// A context on a fresh [`FakeApi`].
+pub fn fake_rknn() -> (RKNN<FakeApi>, Arc<FakeState>) {
+    let state = Arc::new(FakeState::default());
+    let mut model = [0u8; 16];
+    let rknn = RKNN::builder(FakeApi {
+        state: state.clone(),
+    })
+    .model_buffer(&mut model)
+    .build()
+    .unwrap();
+    (rknn, state)
+}
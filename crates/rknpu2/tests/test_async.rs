@@ -0,0 +1,78 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use {
+    common::{FakeApi, FakeState},
+    futures::{FutureExt, executor::block_on},
+    rknpu2::async_rknn::AsyncRknn,
+    std::sync::{Arc, atomic::Ordering},
+};
+
+fn fake_rknn(open: bool) -> (AsyncRknn<FakeApi>, Arc<FakeState>) {
+    let (rknn, state) = common::fake_rknn();
+    if !open {
+        state.close();
+    }
+    (AsyncRknn::new(rknn, 1).unwrap(), state)
+}
+
+#[test]
+fn test_call() {
+    let (rknn, state) = fake_rknn(true);
+
+    for _ in 0..3 {
+        block_on(rknn.call(|rknn| rknn.run())).unwrap().unwrap();
+    }
+    assert_eq!(state.runs.load(Ordering::SeqCst), 3);
+
+    drop(rknn);
+    assert_eq!(*state.destroyed.lock().unwrap(), vec![1]);
+}
+
+#[test]
+fn test_backpressure_and_cancellation() {
+    let (rknn, state) = fake_rknn(false);
+
+    // Picked up by the worker, which then blocks in `run`.
+    let mut first = Box::pin(rknn.call(|rknn| rknn.run()));
+    assert!((&mut first).now_or_never().is_none());
+    state.wait_started(1);
+
+    // Fills the queue, then is dropped before the worker gets to it.
+    let mut queued = Box::pin(rknn.call(|rknn| rknn.run()));
+    assert!((&mut queued).now_or_never().is_none());
+
+    // The queue is full, so this one is never submitted.
+    let mut waiting = Box::pin(rknn.call(|rknn| rknn.run()));
+    assert!((&mut waiting).now_or_never().is_none());
+
+    drop(queued);
+    drop(waiting);
+    state.open();
+
+    block_on(first).unwrap().unwrap();
+    block_on(rknn.call(|rknn| rknn.run())).unwrap().unwrap();
+    assert_eq!(state.runs.load(Ordering::SeqCst), 2);
+}
+
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[test]
+fn test_infer() {
+    use rknpu2::{
+        io::{buffer::Buf, input::OwnedInput},
+        tensor::{TensorFormat, TensorFormatKind},
+    };
+
+    let (rknn, _state) = fake_rknn(true);
+
+    let input = OwnedInput::new(
+        0,
+        Buf::U8(vec![0; 16]),
+        false,
+        TensorFormatKind::NHWC(TensorFormat::NHWC),
+    );
+    let outputs = block_on(rknn.infer(vec![input])).unwrap();
+
+    assert_eq!(outputs, vec![vec![0.0; 4], vec![1.0; 4]]);
+}
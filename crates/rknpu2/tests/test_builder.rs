@@ -0,0 +1,169 @@
+mod common;
+
+use {
+    common::{FakeApi, FakeState},
+    rknpu2::{Error, RKNN, api::RknnInitFlags},
+    std::{fs::File, os::fd::AsRawFd, sync::Arc},
+};
+
+static MODEL_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/mobilenet_v2.rknn"
+);
+
+fn fake_api() -> (FakeApi, Arc<FakeState>) {
+    let state = Arc::new(FakeState::default());
+    (
+        FakeApi {
+            state: state.clone(),
+        },
+        state,
+    )
+}
+
+#[test]
+fn test_builder_extend() {
+    let (api, state) = fake_api();
+    let file = File::open("/dev/null").unwrap();
+    let mut model = [0u8; 64];
+
+    let _rknn = RKNN::builder(api)
+        .model_buffer(&mut model)
+        .model_range(16, 32)
+        .model_buffer_fd(&file, 1)
+        .build()
+        .unwrap();
+
+    let init = &state.inits.lock().unwrap()[0];
+    assert_eq!(init.flags, 0);
+    assert_eq!(init.size, 64);
+    let extend = init.extend.unwrap();
+    assert_eq!(extend.ctx, 0);
+    assert_eq!(extend.real_model_offset, 16);
+    assert_eq!(extend.real_model_size, 32);
+    assert_eq!(extend.model_buffer_fd, file.as_raw_fd());
+    assert_eq!(extend.model_buffer_flags, 1);
+}
+
+#[test]
+fn test_builder_extend_defaults() {
+    let (api, state) = fake_api();
+    let mut model = [0u8; 64];
+
+    let _plain = RKNN::builder(api.clone())
+        .model_buffer(&mut model)
+        .build()
+        .unwrap();
+    let _range = RKNN::builder(api)
+        .model_buffer(&mut model)
+        .model_range(16, 32)
+        .build()
+        .unwrap();
+
+    let inits = state.inits.lock().unwrap();
+    // No extend struct unless an option needs one.
+    assert!(inits[0].extend.is_none());
+    let extend = inits[1].extend.unwrap();
+    assert_eq!(extend.ctx, 0);
+    assert_eq!(extend.model_buffer_fd, 0);
+    assert_eq!(extend.model_buffer_flags, 0);
+}
+
+#[test]
+fn test_share_weights_keeps_source_alive() {
+    let (api, state) = fake_api();
+    let mut model = [0u8; 64];
+
+    let source = Arc::new(
+        RKNN::builder(api.clone())
+            .model_buffer(&mut model)
+            .build()
+            .unwrap(),
+    );
+    let shared = RKNN::builder(api)
+        .model_buffer(&mut model)
+        .flags(RknnInitFlags::ASYNC_MASK)
+        .share_weights_with(&source)
+        .build()
+        .unwrap();
+
+    let init = &state.inits.lock().unwrap()[1];
+    let flags = RknnInitFlags::from(init.flags);
+    assert!(flags.contains(RknnInitFlags::SHARE_WEIGHT_MEM));
+    assert!(flags.contains(RknnInitFlags::ASYNC_MASK));
+    assert_eq!(init.extend.unwrap().ctx, 1);
+
+    drop(source);
+    assert!(state.destroyed.lock().unwrap().is_empty());
+    drop(shared);
+    assert_eq!(*state.destroyed.lock().unwrap(), vec![2, 1]);
+}
+
+#[test]
+fn test_builder_zero_copy_model() {
+    let (api, state) = fake_api();
+    let zero_copy = RknnInitFlags::MODEL_BUFFER_ZERO_COPY;
+
+    // A borrowed buffer does not outlive `build`.
+    let mut model = [0u8; 64];
+    let result = RKNN::builder(api.clone())
+        .model_buffer(&mut model)
+        .flags(zero_copy)
+        .build();
+    assert!(matches!(result, Err(Error::ParamInvalid)));
+    assert!(state.inits.lock().unwrap().is_empty());
+
+    let data: Arc<[u8]> = std::fs::read(MODEL_PATH).unwrap().into();
+    let shared = RKNN::builder(api.clone())
+        .model_shared(data.clone())
+        .flags(zero_copy)
+        .build()
+        .unwrap();
+    let mapped = RKNN::builder(api)
+        .model_file(MODEL_PATH)
+        .unwrap()
+        .flags(zero_copy)
+        .build()
+        .unwrap();
+
+    {
+        let inits = state.inits.lock().unwrap();
+        // The shared buffer is passed as is, not copied.
+        assert_eq!(inits[0].model, data.as_ptr() as usize);
+        assert_eq!(inits[0].size as usize, data.len());
+        assert_eq!(inits[1].size as usize, data.len());
+        let mapped_data =
+            unsafe { std::slice::from_raw_parts(inits[1].model as *const u8, data.len()) };
+        assert_eq!(mapped_data, &data[..]);
+    }
+
+    assert_eq!(Arc::strong_count(&data), 2);
+    drop(shared);
+    assert_eq!(Arc::strong_count(&data), 1);
+    drop(mapped);
+
+    assert!(matches!(
+        RKNN::builder(fake_api().0).model_file("/nonexistent.rknn"),
+        Err(Error::Io(_))
+    ));
+}
+
+#[cfg(feature = "libloading")]
+#[test]
+fn test_with_library_load_error() {
+    let library = "/nonexistent/librknnrt.so";
+    let flags = RknnInitFlags::MODEL_BUFFER_ZERO_COPY;
+
+    assert!(matches!(
+        RKNN::from_file_with_library(library, MODEL_PATH, flags),
+        Err(Error::LibraryLoad(_))
+    ));
+    assert!(matches!(
+        RKNN::from_shared_with_library(library, Arc::from(&[0u8; 16][..]), flags),
+        Err(Error::LibraryLoad(_))
+    ));
+    assert!(matches!(
+        RKNN::new_with_library(library, &mut [0u8; 16], RknnInitFlags::empty()),
+        Err(Error::LibraryLoad(_))
+    ));
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use {
+    common::{FakeApi, FakeState},
+    rknpu2::{Error, RKNN, api::RknnInitFlags, rknn::RunOptions},
+    std::{
+        fs::File,
+        os::fd::{AsRawFd, OwnedFd, RawFd},
+        path::Path,
+        sync::{Arc, atomic::Ordering},
+    },
+};
+
+fn is_open(fd: RawFd) -> bool {
+    Path::new(&format!("/proc/self/fd/{fd}")).exists()
+}
+
+// A single test, so no other test in this binary opens fds concurrently.
+#[test]
+fn test_fences() {
+    let state = Arc::new(FakeState::default());
+    let mut model = [0u8; 16];
+    let rknn = RKNN::builder(FakeApi {
+        state: state.clone(),
+    })
+    .model_buffer(&mut model)
+    .flags(
+        RknnInitFlags::empty()
+            .with_fence_in_outside()
+            .with_fence_out_outside(),
+    )
+    .build()
+    .unwrap();
+    state.fence_out.store(true, Ordering::SeqCst);
+
+    // The output fence is handed to the caller, the input fence is closed.
+    let fence_in = OwnedFd::from(File::open("/dev/null").unwrap());
+    let fence_in_fd = fence_in.as_raw_fd();
+    let out = rknn.run_with(RunOptions::new().fence_in(fence_in)).unwrap();
+    assert!(!is_open(fence_in_fd));
+    let fence_out = out.fence_out.unwrap();
+    assert_eq!(fence_out.as_raw_fd(), state.fences.lock().unwrap()[0]);
+    assert!(is_open(fence_out.as_raw_fd()));
+    drop(fence_out);
+
+    // An output fence produced before a failure is closed.
+    state
+        .run_ret
+        .store(rknpu2_sys::RKNN_ERR_TIMEOUT, Ordering::SeqCst);
+    assert!(matches!(
+        rknn.run_with(RunOptions::new()),
+        Err(Error::RunTimeout { frame_id: 2 })
+    ));
+    state
+        .run_ret
+        .store(rknpu2_sys::RKNN_ERR_FAIL, Ordering::SeqCst);
+    assert!(matches!(rknn.run_with(RunOptions::new()), Err(Error::Fail)));
+    for &fence in &state.fences.lock().unwrap()[1..] {
+        assert!(!is_open(fence));
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(any(feature = "rk3576", feature = "rk35xx"))]
+
+mod common;
+
+use {
+    common::fake_rknn,
+    rknpu2::{
+        Error,
+        io::{
+            buffer::BufMutView,
+            output::{Output, OutputKind},
+        },
+    },
+    std::sync::atomic::Ordering,
+};
+
+#[test]
+fn test_get_outputs_for_skips_frames() {
+    let (rknn, state) = fake_rknn();
+
+    let frames = (0..3)
+        .map(|_| rknn.run_async().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(frames[2].id(), 3);
+
+    let mut logits = [0.0f32; 4];
+    let mut outputs = [
+        Output {
+            index: 0,
+            kind: OutputKind::RuntimePreallocated { want_float: true },
+        },
+        Output {
+            index: 1,
+            kind: OutputKind::Preallocated {
+                buf: BufMutView::F32(&mut logits),
+                want_float: true,
+            },
+        },
+    ];
+    rknn.get_outputs_for(frames[2], &mut outputs).unwrap();
+
+    // Frames 1 and 2 were released, and every attempt asked the runtime to
+    // allocate output 0.
+    assert_eq!(state.releases.load(Ordering::SeqCst), 2);
+    assert_eq!(*state.output_gets.lock().unwrap(), vec![vec![0, 1]; 3]);
+    match &outputs[0].kind {
+        OutputKind::RuntimeAllocated { buf, .. } => assert_eq!(buf.as_slice::<f32>(), &[0.0; 4]),
+        _ => panic!("output 0 was not allocated by the runtime"),
+    }
+    assert_eq!(logits, [1.0; 4]);
+}
+
+#[test]
+fn test_get_outputs_for_stalled_frames() {
+    let (rknn, state) = fake_rknn();
+
+    let frame = rknn.run_async().unwrap();
+    // The runtime reports no frame ids at all.
+    state.pending.lock().unwrap().clear();
+
+    let mut logits = [0.0f32; 4];
+    let mut outputs = [Output {
+        index: 0,
+        kind: OutputKind::Preallocated {
+            buf: BufMutView::F32(&mut logits),
+            want_float: true,
+        },
+    }];
+    assert!(matches!(
+        rknn.get_outputs_for(frame, &mut outputs),
+        Err(Error::FrameUnavailable {
+            requested: 1,
+            received: 0
+        })
+    ));
+    assert_eq!(state.releases.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use {
+    common::fake_rknn,
+    rknpu2::{
+        Error,
+        api::MemAllocFlags,
+        io::{
+            mem::{MemSyncMode, TensorMem},
+            zero_copy::ZeroCopySession,
+        },
+        query::TensorAttrView,
+    },
+    rknpu2_sys::*,
+    std::sync::atomic::Ordering,
+};
+
+#[test]
+fn test_tensor_mem_views() {
+    let (rknn, state) = fake_rknn();
+
+    let mut mem = TensorMem::new(&rknn, 16).unwrap();
+    assert_eq!(mem.size(), 16);
+    assert_eq!(mem.fd(), 3);
+    assert_eq!(mem.phys_addr(), 0x1000);
+
+    mem.as_mut_slice::<f32>().unwrap().fill(1.0);
+    assert_eq!(mem.as_slice::<f32>().unwrap(), &[1.0; 4]);
+    assert_eq!(mem.as_slice::<u8>().unwrap()[..4], 1.0f32.to_ne_bytes());
+    mem.sync(MemSyncMode::ToDevice).unwrap();
+    assert_eq!(
+        *state.synced.lock().unwrap(),
+        vec![_rknn_mem_sync_mode::RKNN_MEMORY_SYNC_TO_DEVICE]
+    );
+
+    drop(mem);
+    assert_eq!(state.mems_destroyed.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_tensor_mem_checks() {
+    let (rknn, state) = fake_rknn();
+
+    let mem = TensorMem::new(&rknn, 6).unwrap();
+    assert!(matches!(
+        mem.as_slice::<u32>(),
+        Err(Error::SizeMismatch {
+            expected: 8,
+            actual: 6
+        })
+    ));
+    assert_eq!(mem.as_slice::<u16>().unwrap().len(), 3);
+
+    state.shift.store(1, Ordering::SeqCst);
+    let mem = TensorMem::new(&rknn, 8).unwrap();
+    assert!(matches!(
+        mem.as_slice::<f32>(),
+        Err(Error::Misaligned { align: 4, .. })
+    ));
+    assert_eq!(mem.as_slice::<i8>().unwrap().len(), 8);
+
+    assert!(matches!(TensorMem::new(&rknn, 0), Err(Error::MallocFailed)));
+    assert_eq!(state.allocated.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_tensor_mem_flags() {
+    let (rknn, state) = fake_rknn();
+
+    let flags = MemAllocFlags::builder()
+        .with_cacheable()
+        .with_try_alloc_sram();
+    let mem = TensorMem::with_flags(&rknn, 32, flags).unwrap();
+    assert_eq!(mem.size(), 32);
+    assert_eq!(
+        *state.alloc_flags.lock().unwrap(),
+        vec![
+            (_rknn_mem_alloc_flags::RKNN_FLAG_MEMORY_CACHEABLE
+                | _rknn_mem_alloc_flags::RKNN_FLAG_MEMORY_TRY_ALLOC_SRAM) as u64
+        ]
+    );
+    assert_eq!(
+        MemAllocFlags::from(RKNN_MEM_FLAG_ALLOC_NO_CONTEXT as u64),
+        MemAllocFlags::NO_CONTEXT
+    );
+}
+
+#[test]
+fn test_zero_copy_session() {
+    let (mut rknn, state) = fake_rknn();
+
+    let mut session = ZeroCopySession::new(&mut rknn).unwrap();
+    assert_eq!(state.bound.lock().unwrap().len(), 3);
+    assert_eq!(session.input_attrs()[0].size_with_stride(), 8);
+    assert_eq!(session.input_mem(0).unwrap().size(), 8);
+    assert_eq!(session.output_mem(0).unwrap().size(), 16);
+    assert!(session.input_mem(1).is_none());
+
+    assert!(matches!(
+        session.input_mut::<u8>(0),
+        Err(Error::TensorTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        session.output::<f32>(2),
+        Err(Error::OutputInvalid)
+    ));
+
+    let input = session.input_mut::<i8>(0).unwrap();
+    assert_eq!(input.len(), 8);
+    input.copy_from_slice(&[1, 2, 0, 0, 3, 4, 0, 0]);
+    session.run().unwrap();
+    assert_eq!(
+        *state.synced.lock().unwrap(),
+        [
+            _rknn_mem_sync_mode::RKNN_MEMORY_SYNC_TO_DEVICE,
+            _rknn_mem_sync_mode::RKNN_MEMORY_SYNC_FROM_DEVICE,
+            _rknn_mem_sync_mode::RKNN_MEMORY_SYNC_FROM_DEVICE
+        ]
+    );
+    assert_eq!(session.output::<f32>(0).unwrap(), &[2.0, 4.0, 6.0, 8.0]);
+    assert_eq!(session.output::<f32>(1).unwrap(), &[2.0, 4.0, 6.0, 8.0]);
+
+    // The context may still write to the bound memory.
+    drop(session);
+    assert_eq!(state.mems_destroyed.load(Ordering::SeqCst), 0);
+    rknn.run().unwrap();
+
+    drop(rknn);
+    assert_eq!(state.mems_destroyed.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_zero_copy_session_rebinds() {
+    let (mut rknn, state) = fake_rknn();
+
+    drop(ZeroCopySession::new(&mut rknn).unwrap());
+    assert_eq!(state.mems_destroyed.load(Ordering::SeqCst), 0);
+
+    // Rebinding every tensor frees the memory the first session left bound.
+    for sessions in 1..=3 {
+        let session = ZeroCopySession::new(&mut rknn).unwrap();
+        assert_eq!(state.mems_destroyed.load(Ordering::SeqCst), 3 * sessions);
+        drop(session);
+    }
+
+    drop(rknn);
+    assert_eq!(state.mems_destroyed.load(Ordering::SeqCst), 12);
+}
+
+#[cfg(any(feature = "rk35xx", feature = "rk3576", feature = "rv110x"))]
+fn memfd(len: u64) -> std::fs::File {
+    use std::os::fd::FromRawFd;
+
+    unsafe extern "C" {
+        fn memfd_create(name: *const std::ffi::c_char, flags: std::ffi::c_uint) -> std::ffi::c_int;
+    }
+
+    let fd = unsafe { memfd_create(c"rknpu2-test".as_ptr(), 0) };
+    assert!(fd >= 0, "memfd_create failed");
+    let file = unsafe { std::fs::File::from_raw_fd(fd) };
+    file.set_len(len).unwrap();
+    file
+}
+
+#[cfg(any(feature = "rk35xx", feature = "rk3576", feature = "rv110x"))]
+#[test]
+fn test_dma_buf_import() {
+    use {
+        rknpu2::io::dma_buf::DmaBufMem,
+        std::os::{
+            fd::{AsFd, AsRawFd},
+            unix::fs::FileExt,
+        },
+    };
+
+    let (rknn, state) = fake_rknn();
+    let frame = memfd(8192);
+    frame.write_all_at(&[9; 4096], 0).unwrap();
+    frame.write_all_at(&[1, 2, 3, 4], 4096).unwrap();
+
+    let mut mem = DmaBufMem::import_mapped(&rknn, frame.as_fd(), 4096, 16).unwrap();
+    assert!(mem.is_mapped());
+    assert_eq!(mem.fd(), frame.as_raw_fd());
+    assert_eq!(mem.offset(), 4096);
+    assert_eq!(mem.size(), 16);
+    // Views start at `offset`, not at the address handed to the runtime.
+    let view = mem.as_slice::<u8>().unwrap();
+    assert_eq!(view.len(), 16);
+    assert_eq!(view[..4], [1, 2, 3, 4]);
+    assert!(!view.contains(&9));
+
+    mem.as_mut_slice::<u8>().unwrap()[4] = 5;
+    let mut byte = [0u8];
+    frame.read_exact_at(&mut byte, 4100).unwrap();
+    assert_eq!(byte, [5]);
+    drop(mem);
+
+    let mem = DmaBufMem::import(&rknn, frame.as_fd(), 0, 16).unwrap();
+    assert!(matches!(mem.as_slice::<u8>(), Err(Error::Unmapped)));
+    drop(mem);
+    assert_eq!(state.mems_destroyed.load(Ordering::SeqCst), 2);
+
+    let mem = DmaBufMem::from_owned_fd(&rknn, memfd(4096).into(), 0, 4096).unwrap();
+    let fd = mem.into_fd().unwrap();
+    assert_eq!(state.mems_destroyed.load(Ordering::SeqCst), 3);
+    assert_eq!(std::fs::File::from(fd).metadata().unwrap().len(), 4096);
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use {
+    common::FakeApi,
+    rknpu2::{
+        Error, RKNN,
+        model::{CompatibilityIssue, ModelFile, Platform},
+        query::Io,
+    },
+};
+
+static MODEL_DATA: &[u8] = include_bytes!("./fixtures/mobilenet_v2.rknn");
+
+#[test]
+fn test_model_header() {
+    let model = ModelFile::parse(MODEL_DATA).unwrap();
+
+    assert_eq!(model.file_version, 6);
+    assert!(model.model_size > 0);
+    assert_eq!(model.generator.as_deref(), Some("RKNPU v2"));
+    assert_eq!(model.toolkit_version.as_deref(), Some("2.3.2"));
+    assert!(
+        model
+            .compiler_version
+            .as_deref()
+            .unwrap()
+            .starts_with("2.3.2")
+    );
+    assert_eq!(model.target_platforms, vec!["rk3588".to_string()]);
+    assert_eq!(model.source_platform.as_deref(), Some("ONNX"));
+}
+
+#[test]
+fn test_model_io() {
+    let model = ModelFile::parse(MODEL_DATA).unwrap();
+
+    assert_eq!(model.inputs.len(), 1);
+    assert_eq!(model.outputs.len(), 1);
+
+    let input = &model.inputs[0];
+    assert_eq!(input.name, "input");
+    assert_eq!(input.io, Io::Input);
+    assert_eq!(input.shape, vec![1, 3, 224, 224]);
+    assert_eq!(input.dtype.as_deref(), Some("float32"));
+    assert_eq!(input.runtime_dtype.as_deref(), Some("int8"));
+    assert_eq!(input.runtime_layout.as_deref(), Some("NHWC"));
+    assert_eq!(input.mean, vec![123.675, 116.28, 103.53]);
+    assert_eq!(input.std.len(), 3);
+
+    let output = &model.outputs[0];
+    assert_eq!(output.name, "output");
+    assert_eq!(output.io, Io::Output);
+    assert_eq!(output.shape, vec![1, 1000]);
+}
+
+#[test]
+fn test_model_quant_tab() {
+    let model = ModelFile::parse(MODEL_DATA).unwrap();
+
+    let input = model.quant_entry("input").unwrap();
+    assert_eq!(input.qtype.as_deref(), Some("asym"));
+    assert_eq!(input.qmethod.as_deref(), Some("layer"));
+    assert_eq!(input.min.len(), 1);
+    assert!(model.quant_entry("output").is_some());
+}
+
+#[test]
+fn test_model_invalid() {
+    assert!(matches!(
+        ModelFile::parse(b"not a model"),
+        Err(Error::ModelFileInvalid(_))
+    ));
+    assert!(matches!(
+        ModelFile::parse(&MODEL_DATA[..1024]),
+        Err(Error::ModelFileInvalid(_))
+    ));
+}
+
+#[test]
+fn test_model_compatibility() {
+    let model = ModelFile::parse(MODEL_DATA).unwrap();
+
+    let report = model.check_compatibility(Platform::Rk3588);
+    assert!(
+        !report
+            .issues
+            .iter()
+            .any(|i| matches!(i, CompatibilityIssue::TargetPlatformUnmatch { .. }))
+    );
+
+    let report = model.check_compatibility(Platform::Rk3566);
+    assert!(!report.is_compatible());
+    assert!(matches!(
+        report.clone().into_result(),
+        Err(Error::TargetPlatformUnmatch)
+    ));
+    assert!(report.to_string().contains("rk3588"));
+
+    let report = model
+        .check_compatibility(Platform::Rk3588)
+        .against_runtime("2.1.0 (967d001cc8@2024-08-07T19:28:19)");
+    assert!(
+        report
+            .issues
+            .iter()
+            .any(|i| matches!(i, CompatibilityIssue::ToolkitNewerThanRuntime { .. }))
+    );
+
+    // Available on every backend without creating a context.
+    let report = RKNN::<FakeApi>::check_compatibility(MODEL_DATA, Platform::Rk3566).unwrap();
+    assert!(!report.is_compatible());
+}
+
+#[test]
+fn test_platform_from_compatible() {
+    assert_eq!(
+        Platform::from_compatible("radxa,rock-5b\0rockchip,rk3588\0"),
+        Some(Platform::Rk3588)
+    );
+    assert_eq!(Platform::from_compatible("brcm,bcm2712\0"), None);
+    assert!(Platform::Rk3568.runs_models_for(Platform::Rk3566));
+}
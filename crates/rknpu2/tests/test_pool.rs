@@ -0,0 +1,68 @@
+#![cfg(feature = "rk3576")]
+
+mod common;
+
+use {
+    common::fake_rknn,
+    rknpu2::{
+        Error,
+        pool::{Dispatch, RK3588_CORES, RknnPool},
+        rknn::NpuCores,
+    },
+    rknpu2_sys::{_rknn_core_mask, RKNN_ERR_PARAM_INVALID},
+    std::sync::atomic::Ordering,
+};
+
+#[test]
+fn test_set_core_mask_error() {
+    let (rknn, state) = fake_rknn();
+
+    rknn.set_core_mask(NpuCores::CORE1).unwrap();
+    state
+        .core_mask_ret
+        .store(RKNN_ERR_PARAM_INVALID, Ordering::SeqCst);
+    assert!(matches!(
+        rknn.set_core_mask(NpuCores::CORE2),
+        Err(Error::ParamInvalid)
+    ));
+
+    // The rejected mask is not recorded.
+    assert_eq!(rknn.core_mask(), NpuCores::CORE1);
+    assert_eq!(
+        *state.core_masks.lock().unwrap(),
+        vec![
+            _rknn_core_mask::RKNN_NPU_CORE_1,
+            _rknn_core_mask::RKNN_NPU_CORE_2
+        ]
+    );
+}
+
+#[test]
+fn test_round_robin_skips_busy() {
+    let (rknn, _state) = fake_rknn();
+    let pool = RknnPool::new(rknn, RK3588_CORES, Dispatch::RoundRobin).unwrap();
+
+    let first = pool.acquire();
+    let second = pool.acquire();
+    assert_eq!((first.index(), second.index()), (0, 1));
+    assert_eq!(second.cores(), NpuCores::CORE1);
+    drop(first);
+    let third = pool.acquire();
+    let fourth = pool.acquire();
+    assert_eq!((third.index(), fourth.index()), (2, 0));
+    drop(third);
+
+    // Context 1 is next in turn but still held, so the free context 2 is taken.
+    assert_eq!(pool.acquire().index(), 2);
+}
+
+#[test]
+fn test_pooled_zero_copy_session() {
+    let (rknn, state) = fake_rknn();
+    let pool = RknnPool::new(rknn, RK3588_CORES, Dispatch::RoundRobin).unwrap();
+
+    let mut pooled = pool.acquire();
+    let mut session = pooled.zero_copy_session().unwrap();
+    session.run().unwrap();
+    assert_eq!(state.bound.lock().unwrap().len(), 3);
+}
@@ -4,7 +4,7 @@ use rknpu2::{
     RKNN,
     api::{Priority, RknnInitFlags},
     query::{
-        InputAttr, InputOutputNum, NativeInputAttr, NativeNC1HWC2InputAttr,
+        InputAttr, InputOutputNum, MemSize, NativeInputAttr, NativeNC1HWC2InputAttr,
         NativeNC1HWC2OutputAttr, NativeNHWCInputAttr, NativeNHWCOutputAttr, NativeOutputAttr,
         SdkVersion, TensorAttrView, output_attr::OutputAttr,
     },
@@ -63,6 +63,15 @@ fn test_sdk_version() {
     assert!(!sdk_version.driver_version().is_empty());
 }
 
+#[test]
+fn test_mem_size() {
+    let rknn = get_rknn();
+    let mem_size = rknn.query::<MemSize>().unwrap();
+
+    assert!(mem_size.total_weight_size() > 0);
+    assert!(mem_size.total_dma_allocated_size() >= mem_size.total_weight_size());
+}
+
 #[test]
 fn test_input_attr() {
     let rknn = get_rknn();
@@ -188,3 +197,29 @@ fn test_native_nc1hwc2_output_attr() {
         QuantTypeKind::AffineAsymmetric(QuantType::QNT_AFFINE_ASYMMETRIC)
     );
 }
+
+#[test]
+fn test_current_attr() {
+    use rknpu2::query::{CurrentInputAttr, CurrentOutputAttr};
+
+    let rknn = get_rknn();
+    let current_input_attr = rknn.query_with_input::<CurrentInputAttr>(0).unwrap();
+    let current_output_attr = rknn.query_with_input::<CurrentOutputAttr>(0).unwrap();
+
+    // A static model keeps its original shapes.
+    assert_eq!(current_input_attr.dims(), &[1, 224, 224, 3]);
+    assert_eq!(current_output_attr.dims(), &[1, 1000]);
+}
+
+#[test]
+fn test_model_info() {
+    let rknn = get_rknn();
+    let info = rknn.model_info().unwrap();
+
+    assert!(!info.api_version.is_empty());
+    assert_eq!(info.inputs.len(), 1);
+    assert_eq!(info.native_inputs.len(), 1);
+    assert_eq!(info.outputs[0].dims(), &[1, 1000]);
+    assert!(info.mem_size.unwrap().total_weight_size() > 0);
+    assert!(info.to_string().contains("output tensors:"));
+}
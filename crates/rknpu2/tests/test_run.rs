@@ -117,4 +117,193 @@ fn test_perf_detail() {
 
     let perf_detail = model.query::<PerfDetail>().unwrap();
     assert!(perf_detail.details().len() > 0);
+    assert!(!perf_detail.layers().is_empty());
+}
+
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+use rknpu2::{
+    Error,
+    api::RKNNAPI,
+    io::{
+        buffer::{BufMutView, BufView},
+        input::Input,
+        output::{Output, OutputKind},
+    },
+    tensor::{TensorFormat, TensorFormatKind},
+};
+
+/// Feed an all-zero 224x224 NHWC image to input 0.
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+fn set_zero_input<A: RKNNAPI>(model: &RKNN<A>) -> Result<(), Error> {
+    let input_buffer = vec![0i8; 224 * 224 * 3];
+    let input = Input::new(
+        0,
+        BufView::I8(&input_buffer),
+        false,
+        TensorFormatKind::NHWC(TensorFormat::NHWC),
+    );
+    model.set_inputs(input)
+}
+
+/// Read output 0 as float logits with `get`.
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+fn read_logits(get: impl FnOnce(&mut [Output]) -> Result<(), Error>) -> Vec<f32> {
+    let mut logits = vec![0.0f32; 1000];
+    let output = Output {
+        index: 0,
+        kind: OutputKind::Preallocated {
+            buf: BufMutView::F32(&mut logits),
+            want_float: true,
+        },
+    };
+    get(&mut [output]).unwrap();
+    logits
+}
+
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[test]
+fn test_try_duplicate() {
+    let model = get_rknn(RknnInitFlags::empty());
+    let duplicate = model.try_duplicate().unwrap();
+    drop(model);
+
+    let handle = std::thread::spawn(move || {
+        set_zero_input(&duplicate).unwrap();
+        duplicate.run().unwrap();
+        read_logits(|outputs| duplicate.get_outputs(outputs))
+    });
+
+    assert_eq!(handle.join().unwrap().len(), 1000);
+}
+
+#[cfg(feature = "rk3576")]
+#[test]
+fn test_pool_run_batch() {
+    use rknpu2::pool::{Dispatch, RK3576_CORES, RknnPool};
+
+    let pool = RknnPool::new(
+        get_rknn(RknnInitFlags::empty()),
+        RK3576_CORES,
+        Dispatch::LeastBusy,
+    )
+    .unwrap();
+    assert_eq!(pool.len(), RK3576_CORES.len());
+
+    let results = pool.run_batch(0..8, |rknn, i| {
+        set_zero_input(rknn).unwrap();
+        rknn.run().unwrap();
+        read_logits(|outputs| rknn.get_outputs(outputs));
+        i
+    });
+
+    assert_eq!(results, (0..8).collect::<Vec<_>>());
+}
+
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[test]
+fn test_run_async() {
+    let model = get_rknn(RknnInitFlags::empty().with_async());
+
+    let mut frames = Vec::new();
+    for _ in 0..2 {
+        set_zero_input(&model).unwrap();
+        frames.push(model.run_async().unwrap());
+    }
+    assert!(frames[0] < frames[1]);
+
+    let logits = read_logits(|outputs| model.get_outputs_for(frames[1], outputs));
+    assert_eq!(logits.len(), 1000);
+}
+
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[test]
+fn test_run_with_timeout() {
+    use std::time::Duration;
+
+    let model = get_rknn(RknnInitFlags::empty());
+    set_zero_input(&model).unwrap();
+    model.run_with_timeout(Duration::from_secs(5)).unwrap();
+}
+
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[test]
+fn test_bench() {
+    use rknpu2::{api::Priority, bench::Bench};
+
+    let report = Bench::new(5)
+        .warmup(1)
+        .run_priorities(&[Priority::Low, Priority::High], |flags| {
+            let model = get_rknn(flags.with_perf_collection());
+            set_zero_input(&model)?;
+            Ok(model)
+        })
+        .unwrap();
+
+    assert_eq!(report.results.len(), 2);
+    for result in &report.results {
+        assert!(result.wall.min > 0.0);
+        assert!(result.wall.p99 <= result.wall.max);
+        assert!(result.npu.is_some());
+        assert!(result.fps > 0.0);
+    }
+}
+
+#[cfg(feature = "rk3576")]
+#[test]
+fn test_set_batch_core_num() {
+    use rknpu2::rknn::NpuCores;
+
+    let model = get_rknn(RknnInitFlags::empty());
+
+    assert!(matches!(model.set_batch_core_num(0), Err(Error::ParamInvalid)));
+    assert!(matches!(
+        model.set_batch_core_num(NpuCores::PLATFORM_CORES + 1),
+        Err(Error::ParamInvalid)
+    ));
+
+    model.set_core_mask(NpuCores::CORE0).unwrap();
+    assert!(matches!(model.set_batch_core_num(2), Err(Error::ParamInvalid)));
+
+    model.set_core_mask(NpuCores::CORE0_1).unwrap();
+    model.set_batch_core_num(2).unwrap();
+    assert!(matches!(
+        model.set_core_mask(NpuCores::CORE1),
+        Err(Error::ParamInvalid)
+    ));
+    assert_eq!(model.core_mask(), NpuCores::CORE0_1);
+}
+
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[test]
+fn test_set_input_shapes_count() {
+    let model = get_rknn(RknnInitFlags::empty());
+
+    let err = model
+        .set_input_shapes(&[&[1, 224, 224, 3], &[1, 224, 224, 3]])
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::SizeMismatch {
+            expected: 1,
+            actual: 2
+        }
+    ));
+}
+
+#[cfg(any(feature = "rk3576", feature = "rk35xx"))]
+#[test]
+fn test_zero_copy_session() {
+    use rknpu2::{io::zero_copy::ZeroCopySession, query::TensorAttrView};
+
+    let mut model = get_rknn(RknnInitFlags::empty());
+    let mut session = ZeroCopySession::new(&mut model).unwrap();
+
+    let input_size = session.input_attrs()[0].size_with_stride() as usize;
+    let input = session.input_mut::<i8>(0).unwrap();
+    assert_eq!(input.len(), input_size);
+    input.fill(0);
+    session.run().unwrap();
+
+    let output = session.output::<i8>(0).unwrap();
+    assert!(output.len() >= 1000);
 }